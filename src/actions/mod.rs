@@ -1,5 +1,6 @@
 use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 
 use crate::actions::game_control::{get_movement, GameControl};
 use crate::{GameState, GameSystems};
@@ -26,6 +27,8 @@ pub struct Actions {
     pub player_movement: Option<Vec2>,
     pub camera_zoom: Option<f32>,
     pub touch_detected: bool,
+    /// where the cursor is in the world, used by manually aimed weapons
+    pub aim_position: Option<Vec2>,
 }
 
 pub fn set_movement_actions(
@@ -33,7 +36,8 @@ pub fn set_movement_actions(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     touch_input: Res<Touches>,
     mut mouse_scroll: EventReader<MouseWheel>,
-    camera: Query<&Camera, With<Camera2d>>,
+    camera: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
 ) {
     let mut player_movement = Vec2::new(
         get_movement(GameControl::Right, &keyboard_input)
@@ -57,7 +61,7 @@ pub fn set_movement_actions(
         }
     } else if let Some(touch_position) = touch_input.first_pressed_position() {
         actions.touch_detected = true;
-        let camera = camera.single();
+        let (camera, _) = camera.single();
         if let Some(viewport_size) = camera.logical_viewport_size() {
             let joystick_position = Vec2::new(viewport_size.x / 2.0, viewport_size.y - 100.0);
             let joystick_offset = touch_position - joystick_position;
@@ -70,6 +74,12 @@ pub fn set_movement_actions(
         *value -= scroll.y;
     }
     actions.camera_zoom = zoom;
+    actions.aim_position = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera.get_single().ok())
+        .and_then(|(cursor, (camera, transform))| camera.viewport_to_world_2d(transform, cursor));
 
     if player_movement != Vec2::ZERO {
         actions.player_movement = Some(player_movement.normalize());
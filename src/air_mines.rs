@@ -0,0 +1,176 @@
+use bevy::prelude::*;
+
+use crate::{
+    collider::{touches_circle, Collider},
+    explosion::{Blast, BlastFilter, ExplosionEvent},
+    health::{Dead, DespawnTimer, Health},
+    loading::WeaponAssets,
+    modifiers::StatModifiers,
+    weapon::{BasicGun, Coord2D, Friendly, Gun, Hostile, Projectile, Velocity},
+    weapon_definition::WeaponDefinition,
+    GameState, GameSystems,
+};
+
+pub struct AirMinesPlugin;
+
+impl Plugin for AirMinesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                arm_mines,
+                trigger_mines::<Friendly, Hostile>,
+                trigger_mines::<Hostile, Friendly>,
+            )
+                .chain()
+                .in_set(GameSystems::Collision)
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            FixedUpdate,
+            lay_mines
+                .run_if(in_state(GameState::Playing))
+                .after(GameSystems::Collision),
+        )
+        .add_systems(OnEnter(GameState::Menu), cleanup_mines);
+    }
+}
+
+/// How long after being dropped before a mine can go off
+const ARM_DELAY: f32 = 1.0;
+/// How far behind the ship mines are dropped
+const DROP_DISTANCE: f32 = 40.0;
+
+pub struct AirMinesKind;
+
+impl BasicGun for AirMinesKind {
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition> {
+        &weapons.air_mines
+    }
+}
+
+pub type AirMines = Gun<AirMinesKind>;
+
+#[derive(Component)]
+struct Mine {
+    laid_by: Entity,
+    /// seconds left until the mine is armed
+    arm_in: f32,
+    blast: Blast,
+}
+
+fn lay_mines(
+    mut commands: Commands,
+    mut layers: Query<
+        (
+            Entity,
+            &mut AirMines,
+            &Transform,
+            &Velocity,
+            Option<&StatModifiers>,
+            Option<&Friendly>,
+            Option<&Hostile>,
+        ),
+        Without<Dead>,
+    >,
+    mines: Query<&Mine>,
+    weapons: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinition>>,
+    time: Res<Time>,
+) {
+    let Some(definition) = definitions.get(AirMinesKind::definition(&weapons)) else {
+        return;
+    };
+    for (laid_by, mut layer, transform, velocity, modifiers, friendly, hostile) in layers.iter_mut()
+    {
+        if layer.cooldown_remaining > 0.0 {
+            layer.cooldown_remaining -= time.delta_seconds();
+            continue;
+        }
+        let stats = definition.level(layer.level).modified_by(modifiers);
+        let mines_out = mines.iter().filter(|mine| mine.laid_by == laid_by).count();
+        if mines_out >= stats.count as usize {
+            continue;
+        }
+        layer.cooldown_remaining = stats.cooldown;
+        let behind =
+            *Coord2D::from(transform.translation) - velocity.0.normalize_or_zero() * DROP_DISTANCE;
+        let mut entity = commands.spawn((
+            SpriteBundle {
+                texture: definition.projectile_sprite.clone(),
+                transform: Transform::from_translation(behind.extend(0.5)),
+                sprite: Sprite {
+                    custom_size: definition.projectile_size,
+                    color: Color::rgba(1.0, 1.0, 1.0, 0.4),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Velocity(Vec2::ZERO),
+            DespawnTimer(stats.lifespan),
+            Mine {
+                laid_by,
+                arm_in: ARM_DELAY,
+                blast: Blast {
+                    radius: stats.radius.unwrap_or(100.0),
+                    damage: stats.damage,
+                    falloff: 0.5,
+                    hurts: BlastFilter::opposing(friendly.is_some()),
+                },
+            },
+        ));
+        if friendly.is_some() {
+            entity.insert(Friendly);
+        }
+        if hostile.is_some() {
+            entity.insert(Hostile);
+        }
+    }
+}
+
+fn arm_mines(mut mines: Query<(&mut Mine, &mut Sprite)>, time: Res<Time>) {
+    for (mut mine, mut sprite) in mines.iter_mut() {
+        if mine.arm_in > 0.0 {
+            mine.arm_in -= time.delta_seconds();
+            if mine.arm_in <= 0.0 {
+                sprite.color.set_a(1.0);
+            }
+        }
+    }
+}
+
+/// Detonates armed mines of team `A` once anything from team `B` flies within half of the blast radius
+fn trigger_mines<A, B>(
+    mut commands: Commands,
+    mines: Query<(Entity, &Mine, &Transform), With<A>>,
+    targets: Query<(&Transform, Option<&Collider>), (With<Health>, With<B>, Without<Projectile>)>,
+    mut explosions: EventWriter<ExplosionEvent>,
+) where
+    A: Component,
+    B: Component,
+{
+    for (entity, mine, transform) in mines.iter() {
+        if mine.arm_in > 0.0 {
+            continue;
+        }
+        let center = *Coord2D::from(transform.translation);
+        let trigger_range = mine.blast.radius / 2.0;
+        if targets
+            .iter()
+            .any(|(target, collider)| touches_circle(collider, target, center, trigger_range))
+        {
+            explosions.send(ExplosionEvent {
+                caused_by: mine.laid_by,
+                center,
+                blast: mine.blast,
+            });
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn cleanup_mines(mut commands: Commands, mines: Query<Entity, With<Mine>>) {
+    for mine in mines.iter() {
+        commands.entity(mine).despawn();
+    }
+}
@@ -0,0 +1,160 @@
+use bevy::prelude::*;
+
+use crate::{
+    enemy::Enemy,
+    health::Dead,
+    loading::{TextureAssets, WeaponAssets},
+    modifiers::StatModifiers,
+    weapon::{
+        spawn_projectile, BasicGun, Coord2D, Friendly, Gun, Projectile, ProjectileBundle,
+        TargetVector, Velocity,
+    },
+    weapon_definition::WeaponDefinition,
+    GameState, GameSystems,
+};
+
+pub struct BoomerangPlugin;
+
+impl Plugin for BoomerangPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            curve_boomerangs
+                .in_set(GameSystems::PreMovement)
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            FixedUpdate,
+            throw_boomerangs
+                .run_if(in_state(GameState::Playing))
+                .after(GameSystems::Collision),
+        );
+    }
+}
+
+/// How close a returning boomerang has to get to be caught by whoever threw it
+const CATCH_DISTANCE: f32 = 30.0;
+/// How fast the boomerang sprite spins in radians per second
+const SPIN: f32 = 15.0;
+
+pub struct BoomerangKind;
+
+impl BasicGun for BoomerangKind {
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition> {
+        &weapons.boomerang
+    }
+}
+
+pub type Boomerang = Gun<BoomerangKind>;
+
+/// A projectile that gets pulled back towards [`Projectile::fired_by`] and is caught when it returns
+#[derive(Component)]
+pub struct ReturnsToShooter {
+    /// how far the projectile travels before it starts coming back
+    range: f32,
+    /// the speed the projectile was thrown at, it never flies faster than this
+    speed: f32,
+    /// set once the projectile has turned around
+    returning: bool,
+}
+
+fn throw_boomerangs(
+    mut commands: Commands,
+    mut throwers: Query<
+        (
+            Entity,
+            &TargetVector,
+            &mut Boomerang,
+            &Transform,
+            Option<&StatModifiers>,
+            Option<&Friendly>,
+            Option<&Enemy>,
+        ),
+        Without<Dead>,
+    >,
+    textures: Res<TextureAssets>,
+    weapons: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinition>>,
+    time: Res<Time>,
+) {
+    let Some(definition) = definitions.get(BoomerangKind::definition(&weapons)) else {
+        return;
+    };
+    let dt = time.delta_seconds();
+    for (fired_by, vector, mut boomerang, transform, modifiers, friendly, enemy) in
+        throwers.iter_mut()
+    {
+        let Some(target_vector) = boomerang.target_vector(vector) else {
+            continue;
+        };
+        let stats = definition.level(boomerang.level).modified_by(modifiers);
+        let range = stats
+            .range
+            .unwrap_or(stats.projectile_velocity * stats.lifespan / 2.0);
+        if boomerang.cooldown_remaining > 0.0 || target_vector.length() > range {
+            boomerang.cooldown_remaining -= dt;
+            continue;
+        }
+        boomerang.cooldown_remaining = stats.cooldown;
+        let direction = target_vector.normalize();
+        let middle = (stats.count - 1) as f32 / 2.0;
+        for index in 0..stats.count {
+            let angle = (index as f32 - middle) * definition.multishot.spread();
+            let bundle = ProjectileBundle::new(
+                fired_by,
+                definition,
+                &stats,
+                transform,
+                Vec2::from_angle(angle).rotate(direction),
+                textures.bullet_impact.clone(),
+            );
+            spawn_projectile(
+                &mut commands,
+                bundle,
+                &stats,
+                friendly.is_some(),
+                enemy.is_some(),
+            )
+            .insert(ReturnsToShooter {
+                range,
+                speed: stats.projectile_velocity,
+                returning: false,
+            });
+        }
+    }
+}
+
+/// Pulls boomerangs back towards whoever threw them and catches them once they return
+fn curve_boomerangs(
+    mut commands: Commands,
+    mut boomerangs: Query<(
+        Entity,
+        &Projectile,
+        &mut ReturnsToShooter,
+        &mut Velocity,
+        &mut Transform,
+    )>,
+    throwers: Query<&Transform, Without<ReturnsToShooter>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+    for (entity, projectile, mut boomerang, mut velocity, mut transform) in boomerangs.iter_mut() {
+        transform.rotate_z(SPIN * dt);
+        let Ok(thrower) = throwers.get(projectile.fired_by) else {
+            continue;
+        };
+        let to_thrower =
+            *Coord2D::from(thrower.translation) - *Coord2D::from(transform.translation);
+        if boomerang.returning && to_thrower.length() < CATCH_DISTANCE {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        // constant pull that brings a boomerang thrown at `speed` to a stop after `range`
+        let pull = boomerang.speed * boomerang.speed / (2.0 * boomerang.range);
+        velocity.0 += to_thrower.normalize_or_zero() * pull * dt;
+        velocity.0 = velocity.0.clamp_length_max(boomerang.speed);
+        if !boomerang.returning && velocity.0.dot(to_thrower) > 0.0 {
+            boomerang.returning = true;
+        }
+    }
+}
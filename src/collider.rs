@@ -0,0 +1,290 @@
+use bevy::prelude::*;
+
+use crate::{
+    health::Health, spatial_index::SpatialIndexed, weapon::Projectile, GameState, GameSystems,
+};
+
+pub struct ColliderPlugin;
+
+impl Plugin for ColliderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            derive_colliders
+                .before(GameSystems::Collision)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// The shape used for every overlap check. The shape is scaled and rotated along with the [`Transform`].
+/// Anything that can be hit and doesn't have one gets a shape that fits its sprite, see [`Collider::fit`]
+#[derive(Component, Clone, Copy, Debug)]
+pub enum Collider {
+    Circle { radius: f32 },
+    Box { half_size: Vec2 },
+}
+
+/// A [`Collider`] placed in the world
+enum Shape {
+    Circle {
+        center: Vec2,
+        radius: f32,
+    },
+    Box {
+        center: Vec2,
+        half_size: Vec2,
+        /// the box's local x and y axes
+        axes: [Vec2; 2],
+    },
+}
+
+impl Collider {
+    /// A circle that fits inside a square sprite of `size`, or a box covering any other sprite
+    pub fn fit(size: Vec2) -> Self {
+        if size.x == size.y {
+            Collider::Circle {
+                radius: size.x / 2.0,
+            }
+        } else {
+            Collider::rect(size)
+        }
+    }
+
+    pub fn rect(size: Vec2) -> Self {
+        Collider::Box {
+            half_size: size / 2.0,
+        }
+    }
+
+    fn shape(&self, transform: &Transform) -> Shape {
+        let center = transform.translation.truncate();
+        let scale = transform.scale.truncate().abs();
+        match *self {
+            Collider::Circle { radius } => Shape::Circle {
+                center,
+                radius: radius * scale.max_element(),
+            },
+            Collider::Box { half_size } => Shape::Box {
+                center,
+                half_size: half_size * scale,
+                axes: [
+                    (transform.rotation * Vec3::X)
+                        .truncate()
+                        .normalize_or_zero(),
+                    (transform.rotation * Vec3::Y)
+                        .truncate()
+                        .normalize_or_zero(),
+                ],
+            },
+        }
+    }
+
+    /// Radius of a circle around the entity that the whole shape fits inside
+    pub fn bounding_radius(&self, transform: &Transform) -> f32 {
+        match self.shape(transform) {
+            Shape::Circle { radius, .. } => radius,
+            Shape::Box { half_size, .. } => half_size.length(),
+        }
+    }
+
+    pub fn overlaps(
+        &self,
+        transform: &Transform,
+        other: &Collider,
+        other_transform: &Transform,
+    ) -> bool {
+        match (self.shape(transform), other.shape(other_transform)) {
+            (
+                Shape::Circle { center, radius },
+                Shape::Circle {
+                    center: other_center,
+                    radius: other_radius,
+                },
+            ) => center.distance_squared(other_center) <= (radius + other_radius).powi(2),
+            (
+                Shape::Circle { center, radius },
+                Shape::Box {
+                    center: box_center,
+                    half_size,
+                    axes,
+                },
+            )
+            | (
+                Shape::Box {
+                    center: box_center,
+                    half_size,
+                    axes,
+                },
+                Shape::Circle { center, radius },
+            ) => {
+                // find the closest point on the box to the circle in the box's local space
+                let offset = center - box_center;
+                let local = Vec2::new(offset.dot(axes[0]), offset.dot(axes[1]));
+                let closest = local.clamp(-half_size, half_size);
+                local.distance_squared(closest) <= radius * radius
+            }
+            (
+                Shape::Box {
+                    center,
+                    half_size,
+                    axes,
+                },
+                Shape::Box {
+                    center: other_center,
+                    half_size: other_half_size,
+                    axes: other_axes,
+                },
+            ) => {
+                // separating axis test, the boxes overlap unless one of their edges splits them apart
+                let offset = other_center - center;
+                let extent = |half_size: Vec2, axes: [Vec2; 2], axis: Vec2| {
+                    half_size.x * axes[0].dot(axis).abs() + half_size.y * axes[1].dot(axis).abs()
+                };
+                axes.into_iter().chain(other_axes).all(|axis| {
+                    offset.dot(axis).abs()
+                        <= extent(half_size, axes, axis) + extent(other_half_size, other_axes, axis)
+                })
+            }
+        }
+    }
+}
+
+/// Whether an entity touches the circle of `radius` around `center`, for area effects like blasts.
+/// Entities without a [`Collider`] are treated as a point at their center
+pub fn touches_circle(
+    collider: Option<&Collider>,
+    transform: &Transform,
+    center: Vec2,
+    radius: f32,
+) -> bool {
+    match collider {
+        Some(collider) => collider.overlaps(
+            transform,
+            &Collider::Circle { radius },
+            &Transform::from_translation(center.extend(0.0)),
+        ),
+        None => transform.translation.truncate().distance(center) < radius,
+    }
+}
+
+/// Makes [`derive_colliders`] give the entity a box the size of its sprite, even when the sprite is square
+#[derive(Component, Default)]
+pub struct BoxCollider;
+
+/// Gives anything that can be hit a collider sized to its sprite
+fn derive_colliders(
+    mut commands: Commands,
+    entities: Query<
+        (Entity, &Sprite, &Handle<Image>, Has<BoxCollider>),
+        (
+            Without<Collider>,
+            Or<(With<Health>, With<Projectile>, With<SpatialIndexed>)>,
+        ),
+    >,
+    images: Res<Assets<Image>>,
+) {
+    for (entity, sprite, image, boxed) in entities.iter() {
+        let size = sprite
+            .custom_size
+            .or_else(|| images.get(image).map(|image| image.size_f32()));
+        if let Some(size) = size {
+            let collider = if boxed {
+                Collider::rect(size)
+            } else {
+                Collider::fit(size)
+            };
+            commands.entity(entity).try_insert(collider);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    use super::*;
+
+    fn at(x: f32, y: f32) -> Transform {
+        Transform::from_xyz(x, y, 0.0)
+    }
+
+    #[test]
+    fn boxes_overlap_when_their_edges_meet() {
+        let square = Collider::rect(Vec2::splat(20.0));
+        assert!(square.overlaps(&at(0.0, 0.0), &square, &at(19.0, 5.0)));
+        assert!(!square.overlaps(&at(0.0, 0.0), &square, &at(21.0, 5.0)));
+    }
+
+    #[test]
+    fn rotated_boxes_reach_further_along_their_diagonal() {
+        let square = Collider::rect(Vec2::splat(20.0));
+        let turned = at(23.0, 0.0).with_rotation(Quat::from_rotation_z(FRAC_PI_4));
+        assert!(!square.overlaps(&at(0.0, 0.0), &square, &at(23.0, 0.0)));
+        assert!(square.overlaps(&at(0.0, 0.0), &square, &turned));
+    }
+
+    #[test]
+    fn boxes_split_by_a_rotated_edge_do_not_overlap() {
+        // the boxes overlap on both world axes, only the rotated box's own axis separates them
+        let square = Collider::rect(Vec2::splat(20.0));
+        let turned = at(22.0, 22.0).with_rotation(Quat::from_rotation_z(FRAC_PI_4));
+        assert!(!square.overlaps(&at(0.0, 0.0), &square, &turned));
+        assert!(!square.overlaps(&turned, &square, &at(0.0, 0.0)));
+    }
+
+    #[test]
+    fn circles_overlap_the_closest_point_on_a_box() {
+        let rect = Collider::rect(Vec2::new(20.0, 10.0));
+        let circle = Collider::Circle { radius: 3.0 };
+        let origin = at(0.0, 0.0);
+        for (x, y, overlaps) in [
+            (12.0, 0.0, true),
+            (14.0, 0.0, false),
+            // near the corner the distance is measured to the corner itself
+            (12.0, 7.0, true),
+            (13.0, 8.0, false),
+        ] {
+            assert_eq!(
+                rect.overlaps(&origin, &circle, &at(x, y)),
+                overlaps,
+                "({x}, {y})"
+            );
+            assert_eq!(
+                circle.overlaps(&at(x, y), &rect, &origin),
+                overlaps,
+                "({x}, {y})"
+            );
+        }
+    }
+
+    #[test]
+    fn circles_check_against_the_rotated_box() {
+        let bar = Collider::rect(Vec2::new(40.0, 4.0));
+        let circle = Collider::Circle { radius: 1.0 };
+        let upright = Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2));
+        assert!(!bar.overlaps(&at(0.0, 0.0), &circle, &at(0.0, 15.0)));
+        assert!(bar.overlaps(&upright, &circle, &at(0.0, 15.0)));
+    }
+
+    #[test]
+    fn circles_touch_the_edge_of_a_big_collider() {
+        let hull = Collider::rect(Vec2::new(100.0, 20.0));
+        let center = Vec2::new(70.0, 0.0);
+        // the center of the hull is outside the circle but its end is inside
+        assert!(touches_circle(Some(&hull), &at(0.0, 0.0), center, 30.0));
+        assert!(!touches_circle(None, &at(0.0, 0.0), center, 30.0));
+    }
+
+    #[test]
+    fn fit_only_uses_circles_for_square_sprites() {
+        assert!(matches!(
+            Collider::fit(Vec2::splat(40.0)),
+            Collider::Circle { radius } if radius == 20.0
+        ));
+        assert!(matches!(
+            Collider::fit(Vec2::new(10.0, 60.0)),
+            Collider::Box { half_size } if half_size == Vec2::new(5.0, 30.0)
+        ));
+    }
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use crate::{
+    collider::Collider,
+    health::{DamageEvent, DamageType, Dead, Health},
+    spatial_index::SpatialIndex,
+    weapon::{Coord2D, Friendly, Hostile, Projectile},
+    GameState, GameSystems,
+};
+
+pub struct ContactDamagePlugin;
+
+impl Plugin for ContactDamagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                ram_targets::<Hostile, Friendly>,
+                ram_targets::<Friendly, Hostile>,
+            )
+                .in_set(GameSystems::Collision)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Damages anything on the other team that this entity flies into
+#[derive(Component)]
+pub struct ContactDamage {
+    damage: i32,
+    /// seconds before the same target can be hit again
+    cooldown: f32,
+    /// damage the rammer takes on every hit, enough of it makes a kamikaze
+    self_damage: i32,
+    /// targets that were hit recently and the time left until they can be hit again
+    recent: Vec<(Entity, f32)>,
+}
+
+impl ContactDamage {
+    pub fn new(damage: i32, cooldown: f32) -> Self {
+        Self {
+            damage,
+            cooldown,
+            self_damage: 0,
+            recent: Vec::new(),
+        }
+    }
+
+    pub fn with_self_damage(mut self, self_damage: i32) -> Self {
+        self.self_damage = self_damage;
+        self
+    }
+}
+
+fn ram_targets<A, B>(
+    mut rammers: Query<
+        (Entity, &Transform, &Collider, &mut ContactDamage),
+        (With<A>, With<Health>, Without<Dead>, Without<Projectile>),
+    >,
+    targets: Query<(&Transform, &Collider), (With<B>, With<Health>, Without<Projectile>)>,
+    index: Res<SpatialIndex>,
+    mut damage_events: EventWriter<DamageEvent>,
+    time: Res<Time>,
+) where
+    A: Component,
+    B: Component,
+{
+    let dt = time.delta_seconds();
+    for (rammer, transform, collider, mut contact) in rammers.iter_mut() {
+        for (_, remaining) in contact.recent.iter_mut() {
+            *remaining -= dt;
+        }
+        contact.recent.retain(|(_, remaining)| *remaining > 0.0);
+        let position = *Coord2D::from(transform.translation);
+        for target in index.candidates(position, collider.bounding_radius(transform)) {
+            let Ok((target_transform, target_collider)) = targets.get(target) else {
+                continue;
+            };
+            if target == rammer
+                || contact.recent.iter().any(|(hit, _)| *hit == target)
+                || !collider.overlaps(transform, target_collider, target_transform)
+            {
+                continue;
+            }
+            let cooldown = contact.cooldown;
+            contact.recent.push((target, cooldown));
+            damage_events.send(DamageEvent {
+                damaged_by: rammer,
+                applied_to: target,
+                amount: contact.damage,
+                damage_type: DamageType::Kinetic,
+                critical: false,
+            });
+            if contact.self_damage > 0 {
+                damage_events.send(DamageEvent {
+                    damaged_by: target,
+                    applied_to: rammer,
+                    amount: contact.self_damage,
+                    damage_type: DamageType::Kinetic,
+                    critical: false,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use bevy_rand::{prelude::WyRand, resource::GlobalEntropy};
+use rand::Rng;
+
+use crate::{weapon::Projectile, weapon_definition::WeaponLevel, GameState, GameSystems};
+
+pub struct CriticalHitsPlugin;
+
+impl Plugin for CriticalHitsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            roll_critical_hits
+                .before(GameSystems::Collision)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Projectiles with this component have a chance of being a critical hit.
+/// The roll happens once when the projectile is fired, so a critical projectile stays critical for every hit
+#[derive(Component, Clone, Copy)]
+pub struct CriticalHit {
+    /// chance from 0.0 to 1.0 of the projectile being a critical hit
+    pub chance: f32,
+    /// how much the damage of a critical hit is multiplied by
+    pub multiplier: f32,
+}
+
+impl CriticalHit {
+    /// The critical hit stats of a weapon, or `None` if it can never crit
+    pub fn from_stats(stats: &WeaponLevel) -> Option<Self> {
+        (stats.crit_chance > 0.0).then_some(CriticalHit {
+            chance: stats.crit_chance,
+            multiplier: stats.crit_multiplier,
+        })
+    }
+
+    /// Rolls for a critical hit, returning the damage to deal and whether it was critical
+    pub fn roll(&self, damage: i32, rng: &mut impl Rng) -> (i32, bool) {
+        if rng.gen_bool(self.chance.clamp(0.0, 1.0) as f64) {
+            ((damage as f32 * self.multiplier).round() as i32, true)
+        } else {
+            (damage, false)
+        }
+    }
+}
+
+fn roll_critical_hits(
+    mut projectiles: Query<(&mut Projectile, &CriticalHit), Added<CriticalHit>>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+) {
+    for (mut projectile, crit) in projectiles.iter_mut() {
+        let (damage, critical) = crit.roll(projectile.damage_amount, &mut *rng);
+        projectile.damage_amount = damage;
+        projectile.critical = critical;
+    }
+}
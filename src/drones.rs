@@ -0,0 +1,109 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{
+    health::Dead,
+    loading::TextureAssets,
+    weapon::{Coord2D, Friendly, PeaShooter, Target, TargetVector},
+    GameState, GameSystems,
+};
+
+pub struct DronesPlugin;
+
+impl Plugin for DronesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (sync_drones, orbit_drones.after(GameSystems::Movement))
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(OnEnter(GameState::Menu), cleanup_drones);
+    }
+}
+
+/// How far away from their owner drones orbit
+const ORBIT_RADIUS: f32 = 80.0;
+/// How fast drones orbit in radians per second
+const ORBIT_SPEED: f32 = 2.0;
+
+/// Gives the entity companion drones that each fire their own pea shooter
+#[derive(Component)]
+pub struct Drones(pub u8);
+
+impl Drones {
+    pub fn new(level: u8) -> Self {
+        Self(level)
+    }
+}
+
+#[derive(Component)]
+struct Drone {
+    owner: Entity,
+    /// position of this drone in the orbit
+    index: usize,
+}
+
+/// Spawns a drone for each level and keeps their pea shooters at the same level
+fn sync_drones(
+    mut commands: Commands,
+    owners: Query<(Entity, &Drones, &Transform), Changed<Drones>>,
+    mut drones: Query<(&Drone, &mut PeaShooter)>,
+    textures: Res<TextureAssets>,
+) {
+    for (owner, level, transform) in owners.iter() {
+        let mut existing = 0;
+        for (drone, mut pea_shooter) in drones.iter_mut() {
+            if drone.owner == owner {
+                existing += 1;
+                pea_shooter.level = level.0;
+            }
+        }
+        for index in existing..level.0 as usize {
+            commands.spawn((
+                SpriteBundle {
+                    texture: textures.player.clone(),
+                    transform: *transform,
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::splat(40.0)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Drone { owner, index },
+                PeaShooter::new(level.0),
+                Target(None),
+                TargetVector(None),
+                Friendly,
+            ));
+        }
+    }
+}
+
+/// Circles drones around their owner and hands them the owner's target
+fn orbit_drones(
+    mut commands: Commands,
+    mut drones: Query<(Entity, &Drone, &mut Transform, &mut Target), Without<Drones>>,
+    owners: Query<(&Transform, &Drones, &Target), Without<Dead>>,
+    time: Res<Time>,
+) {
+    let orbit = time.elapsed_seconds() * ORBIT_SPEED;
+    for (entity, drone, mut transform, mut target) in drones.iter_mut() {
+        let Ok((owner, drones, owner_target)) = owners.get(drone.owner) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let angle = orbit + drone.index as f32 * TAU / drones.0.max(1) as f32;
+        let offset = Vec2::from_angle(angle) * ORBIT_RADIUS;
+        transform.translation =
+            (*Coord2D::from(owner.translation) + offset).extend(owner.translation.z);
+        transform.rotation = owner.rotation;
+        target.0 = owner_target.0;
+    }
+}
+
+fn cleanup_drones(mut commands: Commands, drones: Query<Entity, With<Drone>>) {
+    for drone in drones.iter() {
+        commands.entity(drone).despawn();
+    }
+}
@@ -22,11 +22,15 @@ fn add_death_screen(
     mut commands: Commands,
     enemies_killed: Res<TotalEnemiesKilled>,
     damage_done: Res<TotalDamageDone>,
+    critical_hits: Res<TotalCriticalHits>,
+    healing_done: Res<TotalHealingDone>,
     enemies_alive: Res<EnemiesStillAlive>,
 ) {
     let stats = [
         ("enemies killed", enemies_killed.0),
         ("damage done", damage_done.0),
+        ("critical hits", critical_hits.0),
+        ("healing done", healing_done.0),
         ("enemies alive", enemies_alive.0),
     ]
     .into_iter()
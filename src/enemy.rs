@@ -5,15 +5,19 @@ use bevy_rand::{prelude::WyRand, resource::GlobalEntropy};
 use rand::Rng;
 
 use crate::{
-    health::{DeadTexture, Health, MaxHealth},
+    collider::{touches_circle, BoxCollider, Collider},
+    contact_damage::ContactDamage,
+    health::{Armor, DamageType, Dead, DeadTexture, Health, MaxHealth, Resistances},
+    knockback::{Mass, Staggered},
     leveling::XpWorth,
     loading::TextureAssets,
     player::{OrientTowardsVelocity, Player},
+    status_effects::StatusEffects,
     weapon::{
-        Bile, ConstantAcceleration, Coord2D, Hostile, PeaShooter, Sniper, Target, TargetVector,
-        VMax, Velocity,
+        Accuracy, Bile, ConstantAcceleration, Coord2D, Hostile, PeaShooter, Sniper, Target,
+        TargetVector, VMax, Velocity,
     },
-    GameState,
+    GameState, GameSystems,
 };
 
 pub struct EnemyPlugin;
@@ -24,12 +28,29 @@ pub struct Enemy;
 #[derive(Component)]
 pub struct MoveToTarget;
 
+/// Something that enemies are willing to chase and shoot at
+#[derive(Component)]
+pub struct Targetable {
+    /// enemies go after the highest priority target they can find, picking the closest on ties
+    pub priority: i32,
+    /// only enemies within this distance will notice this target
+    pub range: Option<f32>,
+}
+
+/// Limits how fast a [`MoveToTarget`] entity can turn, in radians per second
+#[derive(Component)]
+pub struct TurnRate(pub f32);
+
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::Menu), cleanup_enemies)
             .add_systems(
                 Update,
-                move_towards_target.run_if(in_state(GameState::Playing)),
+                (
+                    choose_enemy_targets.before(GameSystems::PreMovement),
+                    move_towards_target,
+                )
+                    .run_if(in_state(GameState::Playing)),
             )
             .add_systems(Update, spawn_enemies.run_if(in_state(GameState::Playing)));
     }
@@ -91,6 +112,8 @@ struct RedPlaneBundle {
     move_to_target: MoveToTarget,
     acceleration: ConstantAcceleration,
     vmax: VMax,
+    contact_damage: ContactDamage,
+    mass: Mass,
 }
 
 impl RedPlaneBundle {
@@ -113,6 +136,8 @@ impl RedPlaneBundle {
             move_to_target: MoveToTarget,
             acceleration: ConstantAcceleration(1000.0),
             vmax: VMax(100.0),
+            contact_damage: ContactDamage::new(10, 1.0),
+            mass: Mass(1.0),
         }
     }
 }
@@ -132,6 +157,9 @@ struct MosquitoBundle {
     velocity: Velocity,
     dead_texture: DeadTexture,
     bile: Bile,
+    contact_damage: ContactDamage,
+    resistances: Resistances,
+    mass: Mass,
 }
 
 impl MosquitoBundle {
@@ -158,6 +186,15 @@ impl MosquitoBundle {
             velocity: Velocity(Vec2::splat(0.01)),
             dead_texture: DeadTexture(assets.mosquito_dead.clone()),
             bile: Bile::new(0),
+            // mosquitoes are kamikazes that burst on impact
+            contact_damage: ContactDamage::new(3, 0.5).with_self_damage(2),
+            // bugs melt under lazers and shrug off their own bile
+            resistances: Resistances(vec![
+                (DamageType::Energy, 1.5),
+                (DamageType::Corrosive, 0.5),
+            ]),
+            // light enough to be flung across the screen
+            mass: Mass(0.3),
         }
     }
 }
@@ -177,8 +214,17 @@ struct SailboatBundle {
     velocity: Velocity,
     dead_texture: DeadTexture,
     sniper: Sniper,
+    contact_damage: ContactDamage,
+    armor: Armor,
+    resistances: Resistances,
+    mass: Mass,
+    accuracy: Accuracy,
+    collider: BoxCollider,
 }
 
+/// How far ahead sailboat snipers aim at a moving player. 0.0 never leads, 1.0 never misses a steady target
+const SAILBOAT_ACCURACY: f32 = 0.6;
+
 impl SailboatBundle {
     fn new(assets: &TextureAssets, target: Entity, transform: Transform) -> Self {
         Self {
@@ -202,7 +248,19 @@ impl SailboatBundle {
             vmax: VMax(500.0),
             velocity: Velocity(Vec2::splat(0.01)),
             dead_texture: DeadTexture(assets.mosquito_dead.clone()),
-            sniper: Sniper::new(0),
+            // level 2 is the first sniper level that does damage and keeps firing
+            sniper: Sniper::new(2),
+            contact_damage: ContactDamage::new(5, 1.0),
+            armor: Armor(1),
+            resistances: Resistances(vec![
+                (DamageType::Kinetic, 0.75),
+                (DamageType::Explosive, 1.5),
+            ]),
+            // heavy enough that they barely budge
+            mass: Mass(10.0),
+            accuracy: Accuracy(SAILBOAT_ACCURACY),
+            // the hull is hit along its whole length, not just a circle in the middle
+            collider: BoxCollider,
         }
     }
 }
@@ -259,6 +317,43 @@ fn spawn_enemies(
     }
 }
 
+/// Points every enemy at the most important [`Targetable`] in range, so decoys can pull them away from the player
+fn choose_enemy_targets(
+    mut enemies: Query<(&mut Target, &Transform, Option<&Collider>), (With<Enemy>, Without<Dead>)>,
+    targetables: Query<(Entity, &Targetable, &Transform), Without<Dead>>,
+) {
+    enemies
+        .par_iter_mut()
+        .for_each(|(mut target, transform, collider)| {
+            let position = *Coord2D::from(transform.translation);
+            let best = targetables
+                .iter()
+                .filter(|(_, targetable, other)| {
+                    targetable.range.is_none_or(|range| {
+                        touches_circle(
+                            collider,
+                            transform,
+                            *Coord2D::from(other.translation),
+                            range,
+                        )
+                    })
+                })
+                .map(|(entity, targetable, other)| {
+                    let distance = (*Coord2D::from(other.translation) - position).length();
+                    (entity, targetable, distance)
+                })
+                .max_by(|(_, a, a_distance), (_, b, b_distance)| {
+                    a.priority
+                        .cmp(&b.priority)
+                        .then(b_distance.total_cmp(a_distance))
+                })
+                .map(|(entity, _, _)| entity);
+            if target.0 != best {
+                target.0 = best;
+            }
+        });
+}
+
 #[inline]
 fn lerp_vec2(source: Vec2, target: Vec2, factor: f32) -> Vec2 {
     Vec2::new(
@@ -268,18 +363,36 @@ fn lerp_vec2(source: Vec2, target: Vec2, factor: f32) -> Vec2 {
 }
 
 fn move_towards_target(
-    mut current_enemies: Query<(&mut Velocity, &TargetVector), With<MoveToTarget>>,
+    mut current_enemies: Query<
+        (
+            &mut Velocity,
+            &TargetVector,
+            Option<&TurnRate>,
+            Option<&StatusEffects>,
+        ),
+        (With<MoveToTarget>, Without<Staggered>),
+    >,
     time: Res<Time>,
 ) {
     let delta = time.delta_seconds();
     let lerp_factor = 1.0 - 0.2_f32.powf(delta);
     current_enemies
         .par_iter_mut()
-        .for_each(|(mut velocity, target_vector)| {
+        .for_each(|(mut velocity, target_vector, turn_rate, status)| {
+            if status.is_some_and(|status| status.is_stunned()) {
+                return;
+            }
             let length = velocity.0.length();
             if let Some(target) = target_vector.0 {
-                velocity.0 =
-                    lerp_vec2(velocity.0.normalize(), target.normalize(), lerp_factor) * length;
+                let current = velocity.0.normalize();
+                let desired = target.normalize();
+                velocity.0 = if let Some(turn_rate) = turn_rate {
+                    let max_turn = turn_rate.0 * delta;
+                    let turn = current.angle_between(desired).clamp(-max_turn, max_turn);
+                    Vec2::from_angle(turn).rotate(current) * length
+                } else {
+                    lerp_vec2(current, desired, lerp_factor) * length
+                };
             }
         });
 }
@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+
+use crate::{
+    collider::{touches_circle, Collider},
+    health::{DamageEvent, DamageType, DeathEvent, DespawnTimer, Health},
+    loading::TextureAssets,
+    weapon::{Coord2D, Friendly, Hostile, Projectile},
+    GameState, GameSystems,
+};
+
+pub struct ExplosionPlugin;
+
+impl Plugin for ExplosionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ExplosionEvent>().add_systems(
+            Update,
+            (explode_on_death, detonate)
+                .chain()
+                .after(GameSystems::Collision)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Which side of the fight an explosion is able to damage
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlastFilter {
+    Hostile,
+    Friendly,
+}
+
+impl BlastFilter {
+    /// Filter that only hurts whoever is on the other team from `friendly`
+    pub fn opposing(friendly: bool) -> Self {
+        if friendly {
+            BlastFilter::Hostile
+        } else {
+            BlastFilter::Friendly
+        }
+    }
+
+    fn includes(&self, friendly: bool, hostile: bool) -> bool {
+        match self {
+            BlastFilter::Hostile => hostile,
+            BlastFilter::Friendly => friendly,
+        }
+    }
+}
+
+/// Describes how big and how painful an explosion is
+#[derive(Clone, Copy, Debug)]
+pub struct Blast {
+    pub radius: f32,
+    /// damage dealt at the center of the blast
+    pub damage: i32,
+    /// how much of the damage is lost by the edge of the blast.
+    /// 0.0 deals full damage everywhere, 1.0 fades out to nothing
+    pub falloff: f32,
+    pub hurts: BlastFilter,
+}
+
+impl Blast {
+    /// Damage dealt to something `distance` away from the center of the blast
+    fn damage_at(&self, distance: f32) -> i32 {
+        let scale = 1.0 - self.falloff * (distance / self.radius).clamp(0.0, 1.0);
+        (self.damage as f32 * scale).round() as i32
+    }
+}
+
+/// Sending this event detonates a [`Blast`] at `center`, damaging everything in range
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ExplosionEvent {
+    /// credited as the source of the damage
+    pub caused_by: Entity,
+    pub center: Vec2,
+    pub blast: Blast,
+}
+
+/// Projectiles with this component detonate whenever they hit something
+#[derive(Component, Clone, Copy)]
+pub struct ExplodeOnImpact(pub Blast);
+
+/// Entities with this component detonate when they die
+#[derive(Component, Clone, Copy)]
+pub struct ExplodeOnDeath(pub Blast);
+
+fn explode_on_death(
+    mut deaths: EventReader<DeathEvent>,
+    explosives: Query<(&Transform, &ExplodeOnDeath)>,
+    mut explosions: EventWriter<ExplosionEvent>,
+) {
+    for death in deaths.read() {
+        if let Ok((transform, explosive)) = explosives.get(death.0) {
+            explosions.send(ExplosionEvent {
+                caused_by: death.0,
+                center: *Coord2D::from(transform.translation),
+                blast: explosive.0,
+            });
+        }
+    }
+}
+
+fn detonate(
+    mut commands: Commands,
+    mut explosions: EventReader<ExplosionEvent>,
+    targets: Query<
+        (
+            Entity,
+            &Transform,
+            Option<&Collider>,
+            Option<&Friendly>,
+            Option<&Hostile>,
+        ),
+        (With<Health>, Without<Projectile>),
+    >,
+    mut damage: EventWriter<DamageEvent>,
+    textures: Res<TextureAssets>,
+) {
+    for ExplosionEvent {
+        caused_by,
+        center,
+        blast,
+    } in explosions.read()
+    {
+        commands.spawn((
+            SpriteBundle {
+                texture: textures.bullet_impact.clone(),
+                transform: Transform::from_translation(center.extend(2.0)),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(blast.radius * 2.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            DespawnTimer(0.3),
+        ));
+        for (entity, transform, collider, friendly, hostile) in targets.iter() {
+            if !blast.hurts.includes(friendly.is_some(), hostile.is_some()) {
+                continue;
+            }
+            // big targets are caught by the edge of the blast, damage still falls off from their center
+            if touches_circle(collider, transform, *center, blast.radius) {
+                let distance = (*Coord2D::from(transform.translation) - *center).length();
+                damage.send(DamageEvent {
+                    damaged_by: *caused_by,
+                    applied_to: entity,
+                    amount: blast.damage_at(distance),
+                    damage_type: DamageType::Explosive,
+                    critical: false,
+                });
+            }
+        }
+    }
+}
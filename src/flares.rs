@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use crate::{
+    enemy::Targetable,
+    health::{Dead, DespawnTimer},
+    loading::WeaponAssets,
+    modifiers::StatModifiers,
+    weapon::{BasicGun, ConstantAcceleration, Gun, Velocity},
+    weapon_definition::WeaponDefinition,
+    GameState, GameSystems,
+};
+
+pub struct FlaresPlugin;
+
+impl Plugin for FlaresPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            deploy_flares
+                .run_if(in_state(GameState::Playing))
+                .after(GameSystems::Collision),
+        )
+        .add_systems(OnEnter(GameState::Menu), cleanup_flares);
+    }
+}
+
+/// Enemies always prefer a burning flare over the player
+const FLARE_PRIORITY: i32 = 10;
+
+pub struct FlaresKind;
+
+impl BasicGun for FlaresKind {
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition> {
+        &weapons.flares
+    }
+}
+
+pub type Flares = Gun<FlaresKind>;
+
+/// A decoy that pulls the attention of nearby enemies until it burns out
+#[derive(Component)]
+struct Flare;
+
+fn deploy_flares(
+    mut commands: Commands,
+    mut ships: Query<(&mut Flares, &Transform, &Velocity, Option<&StatModifiers>), Without<Dead>>,
+    weapons: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinition>>,
+    time: Res<Time>,
+) {
+    let Some(definition) = definitions.get(FlaresKind::definition(&weapons)) else {
+        return;
+    };
+    for (mut flares, transform, velocity, modifiers) in ships.iter_mut() {
+        if flares.cooldown_remaining > 0.0 {
+            flares.cooldown_remaining -= time.delta_seconds();
+            continue;
+        }
+        let stats = definition.level(flares.level).modified_by(modifiers);
+        flares.cooldown_remaining = stats.cooldown;
+        let behind = -velocity.0.normalize_or_zero();
+        let middle = (stats.count - 1) as f32 / 2.0;
+        for index in 0..stats.count {
+            let angle = (index as f32 - middle) * definition.multishot.spread();
+            commands.spawn((
+                SpriteBundle {
+                    texture: definition.projectile_sprite.clone(),
+                    transform: transform.with_scale(Vec3::splat(1.0)),
+                    sprite: Sprite {
+                        custom_size: definition.projectile_size,
+                        color: Color::ORANGE,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Flare,
+                Targetable {
+                    priority: FLARE_PRIORITY,
+                    range: stats.radius,
+                },
+                Velocity(Vec2::from_angle(angle).rotate(behind) * stats.projectile_velocity),
+                // slows the flare down as it drifts away
+                ConstantAcceleration(-1.0),
+                DespawnTimer(stats.lifespan),
+            ));
+        }
+    }
+}
+
+fn cleanup_flares(mut commands: Commands, flares: Query<Entity, With<Flare>>) {
+    for flare in flares.iter() {
+        commands.entity(flare).despawn();
+    }
+}
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 
-use crate::{enemy::MoveToTarget, overshield::OvershieldState, GameState};
+use serde::Deserialize;
+
+use crate::{enemy::MoveToTarget, GameState};
 
 pub struct HealthPlugin;
 
@@ -10,11 +12,84 @@ pub struct Health(pub i32);
 #[derive(Component)]
 pub struct MaxHealth(pub i32);
 
+/// What kind of damage is being dealt, so entities can resist it or be weak to it
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DamageType {
+    #[default]
+    Kinetic,
+    Explosive,
+    Energy,
+    Corrosive,
+    /// dealt over time by [`crate::status_effects::StatusKind::Burn`]
+    Fire,
+}
+
 #[derive(Event)]
 pub struct DamageEvent {
     pub damaged_by: Entity,
     pub applied_to: Entity,
     pub amount: i32,
+    pub damage_type: DamageType,
+    /// whether the hit rolled a critical hit, the multiplier is already part of `amount`
+    pub critical: bool,
+}
+
+/// Sent once a [`DamageEvent`] has made it through every [`DamageStage`], with the amount that was actually taken
+#[derive(Event)]
+pub struct DamageAppliedEvent {
+    pub applied_to: Entity,
+    pub amount: i32,
+    pub critical: bool,
+}
+
+/// Incoming damage goes through each of these stages in order before it is taken off of [`Health`].
+/// Plugins can add systems to a stage to change the [`DamageQueue`]
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageStage {
+    /// damage is queued up and anything that ignores it entirely drops it
+    Incoming,
+    Shield,
+    Armor,
+    Resistance,
+    Health,
+}
+
+/// Damage on its way through the [`DamageStage`]s
+pub struct PendingDamage {
+    pub damaged_by: Entity,
+    pub applied_to: Entity,
+    pub damage_type: DamageType,
+    pub amount: f32,
+    pub critical: bool,
+}
+
+/// Every hit that still needs to be applied this frame
+#[derive(Resource, Default)]
+pub struct DamageQueue(pub Vec<PendingDamage>);
+
+/// Flat amount of damage removed from every hit
+#[derive(Component)]
+pub struct Armor(pub i32);
+
+/// Damage multipliers for each [`DamageType`]. Below 1.0 resists the damage, above 1.0 is a weakness
+#[derive(Component)]
+pub struct Resistances(pub Vec<(DamageType, f32)>);
+
+impl Resistances {
+    pub fn multiplier(&self, damage_type: DamageType) -> f32 {
+        self.0
+            .iter()
+            .filter(|(resisted, _)| *resisted == damage_type)
+            .map(|(_, multiplier)| multiplier)
+            .product()
+    }
+}
+
+/// Sent whenever health is restored to an entity
+#[derive(Event)]
+pub struct HealEvent {
+    pub applied_to: Entity,
+    pub amount: i32,
 }
 
 #[derive(Event)]
@@ -29,10 +104,43 @@ pub struct DeadTexture(pub Handle<Image>);
 impl Plugin for HealthPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<DamageEvent>()
+            .add_event::<DamageAppliedEvent>()
             .add_event::<DeathEvent>()
+            .add_event::<HealEvent>()
+            .init_resource::<DamageQueue>()
+            .configure_sets(
+                Update,
+                (
+                    DamageStage::Incoming,
+                    DamageStage::Shield,
+                    DamageStage::Armor,
+                    DamageStage::Resistance,
+                    DamageStage::Health,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    (queue_damage, ignore_while_invulnerable)
+                        .chain()
+                        .in_set(DamageStage::Incoming),
+                    apply_armor.in_set(DamageStage::Armor),
+                    apply_resistances.in_set(DamageStage::Resistance),
+                    (apply_damage, start_invulnerability)
+                        .chain()
+                        .in_set(DamageStage::Health),
+                ),
+            )
             .add_systems(
                 Update,
-                (check_dead, despawn, apply_damage, apply_dead_texture)
+                (
+                    check_dead,
+                    despawn,
+                    apply_dead_texture,
+                    tick_invulnerability,
+                )
                     .run_if(in_state(GameState::Playing)),
             );
     }
@@ -41,6 +149,43 @@ impl Plugin for HealthPlugin {
 #[derive(Component)]
 pub struct DespawnTimer(pub f32);
 
+/// Ignores all damage for `duration` seconds after being hit
+#[derive(Component)]
+pub struct Invulnerability {
+    duration: f32,
+    remaining: f32,
+}
+
+impl Invulnerability {
+    pub fn new(duration: f32) -> Self {
+        Self {
+            duration,
+            remaining: 0.0,
+        }
+    }
+}
+
+/// How many times per second the sprite blinks while invulnerable
+const BLINK_RATE: f32 = 10.0;
+
+fn tick_invulnerability(
+    mut entities: Query<(&mut Invulnerability, &mut Visibility)>,
+    time: Res<Time>,
+) {
+    for (mut invulnerability, mut visibility) in entities.iter_mut() {
+        if invulnerability.remaining <= 0.0 {
+            continue;
+        }
+        invulnerability.remaining -= time.delta_seconds();
+        let blink_on = (invulnerability.remaining * BLINK_RATE * 2.0) as i32 % 2 == 0;
+        *visibility = if invulnerability.remaining > 0.0 && !blink_on {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
+
 fn despawn(
     mut commands: Commands,
     mut despawners: Query<(&mut DespawnTimer, Entity)>,
@@ -78,38 +223,94 @@ fn apply_dead_texture(mut images: Query<(&mut Handle<Image>, &DeadTexture), Adde
     }
 }
 
+/// Pulls this frame's [`DamageEvent`]s into the [`DamageQueue`]
+fn queue_damage(mut incoming_events: EventReader<DamageEvent>, mut queue: ResMut<DamageQueue>) {
+    queue
+        .0
+        .extend(incoming_events.read().map(|event| PendingDamage {
+            damaged_by: event.damaged_by,
+            applied_to: event.applied_to,
+            damage_type: event.damage_type,
+            amount: event.amount as f32,
+            critical: event.critical,
+        }));
+}
+
+/// Drops any damage dealt while invulnerable
+fn ignore_while_invulnerable(
+    mut queue: ResMut<DamageQueue>,
+    invulnerable: Query<&Invulnerability>,
+) {
+    queue.0.retain(|damage| {
+        invulnerable
+            .get(damage.applied_to)
+            .map_or(true, |invulnerability| invulnerability.remaining <= 0.0)
+    });
+}
+
+/// Starts invulnerability once a hit actually takes health, so hits soaked up by a shield don't count
+fn start_invulnerability(
+    mut applied: EventReader<DamageAppliedEvent>,
+    mut invulnerable: Query<&mut Invulnerability>,
+) {
+    for DamageAppliedEvent { applied_to, .. } in applied.read() {
+        if let Ok(mut invulnerability) = invulnerable.get_mut(*applied_to) {
+            invulnerability.remaining = invulnerability.duration;
+        }
+    }
+}
+
+fn apply_armor(mut queue: ResMut<DamageQueue>, armor: Query<&Armor>) {
+    for damage in queue.0.iter_mut() {
+        if let Ok(armor) = armor.get(damage.applied_to) {
+            damage.amount = (damage.amount - armor.0 as f32).max(0.0);
+        }
+    }
+}
+
+fn apply_resistances(mut queue: ResMut<DamageQueue>, resistances: Query<&Resistances>) {
+    for damage in queue.0.iter_mut() {
+        if let Ok(resistances) = resistances.get(damage.applied_to) {
+            damage.amount *= resistances.multiplier(damage.damage_type);
+        }
+    }
+}
+
+/// Takes whatever damage made it through every stage off of [`Health`]
 fn apply_damage(
-    mut incoming_events: EventReader<DamageEvent>,
-    mut health: Query<(&mut Health, Option<&mut OvershieldState>)>,
+    mut queue: ResMut<DamageQueue>,
+    mut health: Query<&mut Health>,
+    mut applied: EventWriter<DamageAppliedEvent>,
 ) {
-    for DamageEvent {
+    for PendingDamage {
         damaged_by,
         applied_to,
+        damage_type,
         amount,
-    } in incoming_events.read()
+        critical,
+    } in queue.0.drain(..)
     {
-        if let Ok((mut target, overshield)) = health.get_mut(*applied_to) {
-            let mut damage = *amount;
-            if let Some(mut overshield) = overshield {
-                overshield.secs_until_recharge = 2.0;
-                let starting_shield = overshield.current_overshield;
-                overshield.current_overshield -= *amount;
-                info!(
-                    "Shield damage {:?}->{:?} Shield: {}-{}={}",
-                    damaged_by, applied_to, starting_shield, damage, overshield.current_overshield
-                );
-                damage = 0;
-                // carry forward the remaining damage
-                if overshield.current_overshield < 0 {
-                    damage = overshield.current_overshield.abs();
-                    overshield.current_overshield = 0;
-                }
-            }
-            target.0 -= damage;
+        let amount = amount.round() as i32;
+        // hits fully soaked up by shields or armor don't count as damage
+        if amount <= 0 {
+            continue;
+        }
+        if let Ok(mut target) = health.get_mut(applied_to) {
+            target.0 -= amount;
             info!(
-                "Damage done {:?}->{:?}: {}. Health remaining: {}",
-                damaged_by, applied_to, damage, target.0
+                "{:?} damage done {:?}->{:?}: {}{}. Health remaining: {}",
+                damage_type,
+                damaged_by,
+                applied_to,
+                amount,
+                if critical { " (critical)" } else { "" },
+                target.0
             );
+            applied.send(DamageAppliedEvent {
+                applied_to,
+                amount,
+                critical,
+            });
         }
     }
 }
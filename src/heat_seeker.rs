@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+
+use crate::{
+    enemy::{Enemy, MoveToTarget, TurnRate},
+    explosion::{Blast, BlastFilter, ExplodeOnImpact},
+    health::{Dead, Health},
+    loading::{TextureAssets, WeaponAssets},
+    modifiers::StatModifiers,
+    player::OrientTowardsVelocity,
+    spatial_index::SpatialIndex,
+    weapon::{
+        spawn_projectile, BasicGun, Coord2D, Friendly, Gun, Hostile, Projectile, ProjectileBundle,
+        Target, TargetVector,
+    },
+    weapon_definition::WeaponDefinition,
+    GameState, GameSystems,
+};
+
+pub struct HeatSeekerPlugin;
+
+impl Plugin for HeatSeekerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                retarget_missiles::<Friendly, Hostile>,
+                retarget_missiles::<Hostile, Friendly>,
+            )
+                .before(GameSystems::PreMovement)
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            FixedUpdate,
+            launch_missiles
+                .run_if(in_state(GameState::Playing))
+                .after(GameSystems::Collision),
+        );
+    }
+}
+
+/// How fast a missile can turn towards its target in radians per second
+const MISSILE_TURN_RATE: f32 = 3.0;
+
+pub struct HeatSeekerKind;
+
+impl BasicGun for HeatSeekerKind {
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition> {
+        &weapons.heat_seeker
+    }
+}
+
+pub type HeatSeeker = Gun<HeatSeekerKind>;
+
+/// A projectile that locks onto a [`Target`] and picks a new one if its target dies
+#[derive(Component)]
+pub struct Missile;
+
+fn launch_missiles(
+    mut commands: Commands,
+    mut launchers: Query<
+        (
+            Entity,
+            &Target,
+            &TargetVector,
+            &mut HeatSeeker,
+            &Transform,
+            Option<&StatModifiers>,
+            Option<&Friendly>,
+            Option<&Enemy>,
+        ),
+        Without<Dead>,
+    >,
+    textures: Res<TextureAssets>,
+    weapons: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinition>>,
+    time: Res<Time>,
+) {
+    let Some(definition) = definitions.get(HeatSeekerKind::definition(&weapons)) else {
+        return;
+    };
+    let dt = time.delta_seconds();
+    for (fired_by, target, vector, mut launcher, transform, modifiers, friendly, enemy) in
+        launchers.iter_mut()
+    {
+        let Some(target_vector) = launcher.target_vector(vector) else {
+            continue;
+        };
+        let stats = definition.level(launcher.level).modified_by(modifiers);
+        let estimated_distance = stats.lifespan * stats.projectile_velocity;
+        if launcher.cooldown_remaining > 0.0 || target_vector.length() > estimated_distance {
+            launcher.cooldown_remaining -= dt;
+            continue;
+        }
+        launcher.cooldown_remaining = stats.cooldown;
+        let damage = stats.damage;
+        let radius = stats.radius.unwrap_or(50.0);
+        let direction = target_vector.normalize();
+        let middle = (stats.count - 1) as f32 / 2.0;
+        for index in 0..stats.count {
+            let angle = (index as f32 - middle) * definition.multishot.spread();
+            let bundle = ProjectileBundle::new(
+                fired_by,
+                definition,
+                &stats,
+                transform,
+                Vec2::from_angle(angle).rotate(direction),
+                textures.bullet_impact.clone(),
+            );
+            spawn_projectile(
+                &mut commands,
+                bundle,
+                &stats,
+                friendly.is_some(),
+                enemy.is_some(),
+            )
+            .insert((
+                OrientTowardsVelocity,
+                Missile,
+                Target(launcher.target(target)),
+                TargetVector(None),
+                MoveToTarget,
+                TurnRate(MISSILE_TURN_RATE),
+                ExplodeOnImpact(Blast {
+                    radius,
+                    // the direct hit already dealt full damage
+                    damage: damage / 2,
+                    falloff: 0.5,
+                    hurts: BlastFilter::opposing(friendly.is_some()),
+                }),
+            ));
+        }
+    }
+}
+
+/// Locks missiles onto the closest `B` whenever their current target is gone
+fn retarget_missiles<A, B>(
+    mut missiles: Query<(&mut Target, &Transform), (With<Missile>, With<A>)>,
+    targets: Query<&Transform, (With<Health>, With<B>, Without<Projectile>)>,
+    index: Res<SpatialIndex>,
+) where
+    A: Component,
+    B: Component,
+{
+    for (mut target, transform) in missiles.iter_mut() {
+        if target.0.is_some_and(|entity| targets.contains(entity)) {
+            continue;
+        }
+        let position = *Coord2D::from(transform.translation);
+        target.0 = index
+            .nearest(position, f32::INFINITY, |entity| {
+                let other = targets.get(entity).ok()?;
+                Some((*Coord2D::from(other.translation) - position).length())
+            })
+            .map(|(entity, _)| entity);
+    }
+}
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::{
     actions::Actions,
-    health::{Health, MaxHealth},
+    health::{HealEvent, Health, MaxHealth},
     leveling::{xp_required_for_level, Level, Xp},
     overshield::OvershieldState,
     player::Player,
@@ -22,6 +22,7 @@ impl Plugin for HudPlugin {
                 (
                     update_shield_bar,
                     update_health_bar,
+                    tint_health_bar_when_healing,
                     update_time_text,
                     update_enemy_counter_text,
                     update_xp_bar,
@@ -88,12 +89,7 @@ fn add_hud(mut commands: Commands, controls: Res<Actions>) {
                         height: Val::Vh(1.0),
                         ..Default::default()
                     },
-                    background_color: BackgroundColor(Color::Rgba {
-                        red: 1.0,
-                        green: 0.1,
-                        blue: 0.1,
-                        alpha: 1.0,
-                    }),
+                    background_color: BackgroundColor(HEALTH_BAR_COLOR),
                     ..Default::default()
                 },
                 HealthBar,
@@ -192,6 +188,34 @@ fn update_health_bar(
     }
 }
 
+const HEALTH_BAR_COLOR: Color = Color::rgb(1.0, 0.1, 0.1);
+const HEALING_COLOR: Color = Color::rgb(0.1, 1.0, 0.3);
+
+/// Flashes the health bar green while the player is being healed, fading back to red afterwards
+fn tint_health_bar_when_healing(
+    mut healthbar: Query<&mut BackgroundColor, With<HealthBar>>,
+    mut heals: EventReader<HealEvent>,
+    player: Query<Entity, With<Player>>,
+    time: Res<Time>,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    let healed = heals.read().any(|heal| heal.applied_to == player);
+    for mut color in healthbar.iter_mut() {
+        color.0 = if healed {
+            HEALING_COLOR
+        } else {
+            let fade = (time.delta_seconds() * 4.0).min(1.0);
+            Color::rgb(
+                color.0.r().lerp(HEALTH_BAR_COLOR.r(), fade),
+                color.0.g().lerp(HEALTH_BAR_COLOR.g(), fade),
+                color.0.b().lerp(HEALTH_BAR_COLOR.b(), fade),
+            )
+        };
+    }
+}
+
 fn update_shield_bar(
     mut shield_bar: Query<&mut Style, With<ShieldBar>>,
     shield: Query<&OvershieldState, Changed<OvershieldState>>,
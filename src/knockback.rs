@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use crate::{
+    health::Dead,
+    weapon::{Projectile, VMax, Velocity},
+    GameState, GameSystems,
+};
+
+pub struct KnockbackPlugin;
+
+impl Plugin for KnockbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<KnockbackEvent>().add_systems(
+            Update,
+            (
+                apply_knockback.after(GameSystems::Collision),
+                recover_from_knockback.in_set(GameSystems::PreMovement),
+            )
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// How long something is thrown off course after being knocked back
+const STAGGER_SECONDS: f32 = 0.5;
+/// Fraction of the speed above [`VMax`] that is still left after a second of recovering
+const RECOVERY_RATE: f32 = 0.02;
+/// Things that steer by turning their velocity are never knocked to a complete stop
+const MIN_SPEED: f32 = 0.01;
+
+/// Projectiles with this component push whatever they hit along the direction they are travelling.
+/// The strength is the speed given to something with a [`Mass`] of 1
+#[derive(Component, Clone, Copy)]
+pub struct Knockback(pub f32);
+
+/// How hard something is to knock back. Anything without one has a mass of 1
+#[derive(Component)]
+pub struct Mass(pub f32);
+
+#[derive(Event)]
+pub struct KnockbackEvent {
+    pub applied_to: Entity,
+    /// change in velocity for something with a [`Mass`] of 1
+    pub impulse: Vec2,
+}
+
+/// Still recovering from a knockback. While staggered an entity stops steering and accelerating,
+/// and any speed over its [`VMax`] bleeds off until it is back in control
+#[derive(Component)]
+pub struct Staggered {
+    remaining: f32,
+}
+
+fn apply_knockback(
+    mut commands: Commands,
+    mut events: EventReader<KnockbackEvent>,
+    mut targets: Query<
+        (&mut Velocity, Option<&Mass>),
+        (With<VMax>, Without<Projectile>, Without<Dead>),
+    >,
+) {
+    for KnockbackEvent {
+        applied_to,
+        impulse,
+    } in events.read()
+    {
+        let Ok((mut velocity, mass)) = targets.get_mut(*applied_to) else {
+            continue;
+        };
+        let mass = mass.map(|m| m.0).unwrap_or(1.0).max(f32::EPSILON);
+        let pushed = velocity.0 + *impulse / mass;
+        velocity.0 = if pushed.length() > MIN_SPEED {
+            pushed
+        } else {
+            impulse.normalize_or_zero() * MIN_SPEED
+        };
+        commands.entity(*applied_to).try_insert(Staggered {
+            remaining: STAGGER_SECONDS,
+        });
+    }
+}
+
+fn recover_from_knockback(
+    mut commands: Commands,
+    mut staggered: Query<(Entity, &mut Staggered, &mut Velocity, &VMax)>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+    let decay = RECOVERY_RATE.powf(dt);
+    for (entity, mut stagger, mut velocity, vmax) in staggered.iter_mut() {
+        let speed = velocity.0.length();
+        if speed > vmax.0 {
+            velocity.0 *= (vmax.0 + (speed - vmax.0) * decay) / speed;
+        }
+        stagger.remaining -= dt;
+        if stagger.remaining <= 0.0 {
+            commands.entity(entity).remove::<Staggered>();
+        }
+    }
+}
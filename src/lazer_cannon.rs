@@ -0,0 +1,189 @@
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_rand::{prelude::WyRand, resource::GlobalEntropy};
+
+use crate::{
+    collider::Collider,
+    critical_hits::CriticalHit,
+    health::{DamageEvent, Dead, Health},
+    loading::WeaponAssets,
+    modifiers::StatModifiers,
+    weapon::{BasicGun, Coord2D, Friendly, Gun, Hostile, Projectile, Velocity},
+    weapon_definition::WeaponDefinition,
+    GameState, GameSystems,
+};
+
+pub struct LazerCannonPlugin;
+
+impl Plugin for LazerCannonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_beams,
+                aim_beams.after(GameSystems::Movement),
+                fire_lazers::<Friendly, Hostile>.in_set(GameSystems::Collision),
+                fire_lazers::<Hostile, Friendly>.in_set(GameSystems::Collision),
+            )
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(OnEnter(GameState::Menu), cleanup_beams);
+    }
+}
+
+/// How wide the beam is drawn and how wide of a path it damages
+const BEAM_WIDTH: f32 = 10.0;
+/// Rough radius of ships that don't have a [`Collider`] yet
+const TARGET_RADIUS: f32 = 20.0;
+
+pub struct LazerCannonKind;
+
+impl BasicGun for LazerCannonKind {
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition> {
+        &weapons.lazer_cannon
+    }
+}
+
+pub type LazerCannon = Gun<LazerCannonKind>;
+
+/// The visible beam of a [`LazerCannon`], stretched out in front of whoever is firing it
+#[derive(Component)]
+struct Beam {
+    fired_by: Entity,
+}
+
+/// Length of the beam fired by `cannon`
+fn beam_length(cannon: &LazerCannon, definition: &WeaponDefinition) -> f32 {
+    definition.level(cannon.level).range.unwrap_or(300.0)
+}
+
+/// Checks if the segment from `start` to `end` passes within `radius` of `center`
+fn segment_hits_circle(start: Vec2, end: Vec2, center: Vec2, radius: f32) -> bool {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    let t = if length_squared > 0.0 {
+        ((center - start).dot(segment) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let closest = start + segment * t;
+    closest.distance_squared(center) < radius * radius
+}
+
+fn spawn_beams(
+    mut commands: Commands,
+    cannons: Query<Entity, Added<LazerCannon>>,
+    weapons: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinition>>,
+) {
+    let Some(definition) = definitions.get(LazerCannonKind::definition(&weapons)) else {
+        return;
+    };
+    for fired_by in cannons.iter() {
+        commands.spawn((
+            SpriteBundle {
+                texture: definition.projectile_sprite.clone(),
+                sprite: Sprite {
+                    color: Color::rgba(1.0, 0.2, 0.2, 0.8),
+                    anchor: Anchor::BottomCenter,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Beam { fired_by },
+        ));
+    }
+}
+
+/// Keeps each beam attached to the front of its ship, and removes beams whose ship is gone
+fn aim_beams(
+    mut commands: Commands,
+    mut beams: Query<(Entity, &Beam, &mut Transform, &mut Sprite), Without<LazerCannon>>,
+    cannons: Query<(&Transform, &Velocity, &LazerCannon), Without<Dead>>,
+    weapons: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinition>>,
+) {
+    let Some(definition) = definitions.get(LazerCannonKind::definition(&weapons)) else {
+        return;
+    };
+    for (entity, beam, mut transform, mut sprite) in beams.iter_mut() {
+        let Ok((ship, velocity, cannon)) = cannons.get(beam.fired_by) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let heading = velocity.0.normalize_or_zero();
+        if heading == Vec2::ZERO {
+            continue;
+        }
+        transform.translation = Coord2D::from(ship.translation).extend(ship.translation.z - 0.1);
+        transform.rotation = Quat::from_rotation_arc(Vec3::Y, heading.extend(0.0));
+        sprite.custom_size = Some(Vec2::new(BEAM_WIDTH, beam_length(cannon, definition)));
+    }
+}
+
+/// Damages every `B` the beams of team `A` pass through, once per weapon cooldown
+fn fire_lazers<A, B>(
+    mut cannons: Query<
+        (
+            Entity,
+            &mut LazerCannon,
+            &Transform,
+            &Velocity,
+            Option<&StatModifiers>,
+        ),
+        (With<A>, Without<Dead>),
+    >,
+    targets: Query<
+        (Entity, &Transform, Option<&Collider>),
+        (With<Health>, With<B>, Without<Projectile>),
+    >,
+    mut damage_events: EventWriter<DamageEvent>,
+    weapons: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinition>>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    time: Res<Time>,
+) where
+    A: Component,
+    B: Component,
+{
+    let Some(definition) = definitions.get(LazerCannonKind::definition(&weapons)) else {
+        return;
+    };
+    for (fired_by, mut cannon, transform, velocity, modifiers) in cannons.iter_mut() {
+        cannon.cooldown_remaining -= time.delta_seconds();
+        if cannon.cooldown_remaining > 0.0 {
+            continue;
+        }
+        let stats = definition.level(cannon.level).modified_by(modifiers);
+        cannon.cooldown_remaining = stats.cooldown;
+        let start = *Coord2D::from(transform.translation);
+        let end = start + velocity.0.normalize_or_zero() * beam_length(&cannon, definition);
+        let crit = CriticalHit::from_stats(&stats);
+        for (entity, target, collider) in targets.iter() {
+            let center = *Coord2D::from(target.translation);
+            let radius = collider
+                .map(|collider| collider.bounding_radius(target))
+                .unwrap_or(TARGET_RADIUS);
+            if entity != fired_by
+                && segment_hits_circle(start, end, center, radius + BEAM_WIDTH / 2.0)
+            {
+                // every target the beam passes through rolls separately
+                let (amount, critical) = crit
+                    .map(|crit| crit.roll(stats.damage, &mut *rng))
+                    .unwrap_or((stats.damage, false));
+                damage_events.send(DamageEvent {
+                    damaged_by: fired_by,
+                    applied_to: entity,
+                    amount,
+                    damage_type: definition.damage_type,
+                    critical,
+                });
+            }
+        }
+    }
+}
+
+fn cleanup_beams(mut commands: Commands, beams: Query<Entity, With<Beam>>) {
+    for beam in beams.iter() {
+        commands.entity(beam).despawn();
+    }
+}
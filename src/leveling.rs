@@ -1,12 +1,14 @@
 use bevy::prelude::*;
 
 use crate::{
+    collider::Collider,
     enemy::{Enemy, MoveToTarget},
     health::DeathEvent,
     loading::TextureAssets,
     player::Player,
-    weapon::{ConstantAcceleration, Target, TargetVector, Velocity},
-    GameState,
+    spatial_index::{SpatialIndex, SpatialIndexed},
+    weapon::{ConstantAcceleration, Coord2D, Target, TargetVector, Velocity},
+    GameState, GameSystems,
 };
 
 pub(crate) struct LevelSystemPlugin;
@@ -15,7 +17,12 @@ impl Plugin for LevelSystemPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             FixedUpdate,
-            (award_player_xp, run_level_ups, xp_collisions).run_if(in_state(GameState::Playing)),
+            (
+                award_player_xp,
+                run_level_ups,
+                xp_collisions.after(GameSystems::Collision),
+            )
+                .run_if(in_state(GameState::Playing)),
         );
     }
 }
@@ -56,6 +63,7 @@ fn award_player_xp(
                     ..Default::default()
                 },
                 XpPellet(xp.clone()),
+                SpatialIndexed,
                 Velocity(Vec2::new(0.0, 100.0)),
                 ConstantAcceleration(1.0),
                 MoveToTarget,
@@ -68,14 +76,20 @@ fn award_player_xp(
 
 fn xp_collisions(
     mut commands: Commands,
-    mut pellets: Query<(Entity, &Transform, &mut XpPellet)>,
-    mut player: Query<(&Transform, &mut Xp), With<Player>>,
+    mut pellets: Query<(&Transform, &Collider, &mut XpPellet)>,
+    mut player: Query<(&Transform, &Collider, &mut Xp), With<Player>>,
+    index: Res<SpatialIndex>,
 ) {
-    let Ok((player_transform, mut player_xp)) = player.get_single_mut() else {
+    let Ok((player_transform, player_collider, mut player_xp)) = player.get_single_mut() else {
         return;
     };
-    for (entity, pellet_transform, mut xp) in pellets.iter_mut() {
-        if (player_transform.translation - pellet_transform.translation).length_squared() < 1000.0 {
+    let position = *Coord2D::from(player_transform.translation);
+    let reach = player_collider.bounding_radius(player_transform);
+    for entity in index.candidates(position, reach) {
+        let Ok((pellet_transform, pellet_collider, mut xp)) = pellets.get_mut(entity) else {
+            continue;
+        };
+        if player_collider.overlaps(player_transform, pellet_collider, pellet_transform) {
             player_xp.0 += xp.0 .0;
             xp.0 .0 = 0;
             commands.entity(entity).despawn();
@@ -1,24 +1,43 @@
 #![allow(clippy::type_complexity)]
 
 mod actions;
+mod air_mines;
 mod audio;
 pub(crate) mod background_image;
+mod boomerang;
 mod clouds;
+pub(crate) mod collider;
+mod contact_damage;
+mod critical_hits;
+mod drones;
 mod end_game;
 pub(crate) mod enemy;
+pub(crate) mod explosion;
+mod flares;
 pub(crate) mod follow_camera;
 pub(crate) mod health;
+mod heat_seeker;
 mod hud;
+mod knockback;
+mod lazer_cannon;
 pub(crate) mod leveling;
 mod loading;
 mod menu;
+pub(crate) mod modifiers;
+mod nanobots;
 pub(crate) mod overshield;
 pub(crate) mod pause_menu;
 mod player;
 pub(crate) mod power_ups;
+mod spatial_index;
+mod squadron;
 mod stats;
+pub(crate) mod status_effects;
+mod tactical_nuke;
+mod targeting;
 mod waves;
 pub(crate) mod weapon;
+pub(crate) mod weapon_definition;
 
 use crate::actions::ActionsPlugin;
 use crate::audio::InternalAudioPlugin;
@@ -26,24 +45,42 @@ use crate::loading::LoadingPlugin;
 use crate::menu::MenuPlugin;
 use crate::player::PlayerPlugin;
 
+use air_mines::AirMinesPlugin;
 use background_image::BackgroundPlugin;
 #[cfg(debug_assertions)]
 use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
 use bevy::prelude::*;
 use bevy_rand::{plugin::EntropyPlugin, prelude::WyRand};
+use boomerang::BoomerangPlugin;
 use clouds::CloudPlugin;
+use collider::ColliderPlugin;
+use contact_damage::ContactDamagePlugin;
+use critical_hits::CriticalHitsPlugin;
+use drones::DronesPlugin;
 use end_game::EndGamePlugin;
 use enemy::EnemyPlugin;
+use explosion::ExplosionPlugin;
+use flares::FlaresPlugin;
 use follow_camera::FollowCameraPlugin;
 use health::HealthPlugin;
+use heat_seeker::HeatSeekerPlugin;
 use hud::HudPlugin;
+use knockback::KnockbackPlugin;
+use lazer_cannon::LazerCannonPlugin;
 use leveling::LevelSystemPlugin;
+use nanobots::NanobotsPlugin;
 use overshield::OvershieldPlugin;
 use pause_menu::PausePlugin;
 use power_ups::PowerupPlugin;
+use spatial_index::SpatialIndexPlugin;
+use squadron::SquadronPlugin;
 use stats::StatsPlugin;
+use status_effects::StatusEffectsPlugin;
+use tactical_nuke::TacticalNukePlugin;
+use targeting::TargetingPlugin;
 use waves::WavesPlugin;
 use weapon::WeaponPlugin;
+use weapon_definition::WeaponDefinitionPlugin;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
 pub(crate) enum GameSystems {
@@ -98,6 +135,7 @@ impl Plugin for GamePlugin {
                 WavesPlugin,
                 PowerupPlugin,
                 CloudPlugin,
+                FlaresPlugin,
             ))
             .add_plugins((
                 BackgroundPlugin,
@@ -106,6 +144,25 @@ impl Plugin for GamePlugin {
                 StatsPlugin,
                 EndGamePlugin,
                 LevelSystemPlugin,
+                WeaponDefinitionPlugin,
+                BoomerangPlugin,
+                HeatSeekerPlugin,
+                ExplosionPlugin,
+                TacticalNukePlugin,
+                LazerCannonPlugin,
+                AirMinesPlugin,
+                DronesPlugin,
+                NanobotsPlugin,
+            ))
+            .add_plugins((
+                SquadronPlugin,
+                StatusEffectsPlugin,
+                SpatialIndexPlugin,
+                ColliderPlugin,
+                ContactDamagePlugin,
+                CriticalHitsPlugin,
+                KnockbackPlugin,
+                TargetingPlugin,
             ));
 
         #[cfg(debug_assertions)]
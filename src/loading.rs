@@ -1,3 +1,4 @@
+use crate::weapon_definition::WeaponDefinition;
 use crate::GameState;
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
@@ -14,7 +15,8 @@ impl Plugin for LoadingPlugin {
             LoadingState::new(GameState::Loading)
                 .continue_to_state(GameState::Menu)
                 .load_collection::<AudioAssets>()
-                .load_collection::<TextureAssets>(),
+                .load_collection::<TextureAssets>()
+                .load_collection::<WeaponAssets>(),
         );
     }
 }
@@ -38,18 +40,12 @@ pub struct TextureAssets {
     pub red_plane: Handle<Image>,
     #[asset(path = "textures/redplane_dead.png")]
     pub red_plane_dead: Handle<Image>,
-    #[asset(path = "textures/bullet.png")]
-    pub bullet: Handle<Image>,
     #[asset(path = "textures/bullet_impact.png")]
     pub bullet_impact: Handle<Image>,
-    #[asset(path = "textures/pea.png")]
-    pub pea: Handle<Image>,
     #[asset(path = "textures/player.png")]
     pub player: Handle<Image>,
     #[asset(path = "textures/player_dead.png")]
     pub player_dead: Handle<Image>,
-    #[asset(path = "textures/rocket.png")]
-    pub rocket: Handle<Image>,
     #[asset(path = "textures/cloud_1.png")]
     pub cloud_1: Handle<Image>,
     #[asset(path = "textures/cloud_2.png")]
@@ -64,6 +60,30 @@ pub struct TextureAssets {
     pub mosquito_dead: Handle<Image>,
     #[asset(path = "textures/xp_orb.png")]
     pub xp_orb: Handle<Image>,
-    #[asset(path = "textures/bile.png")]
-    pub bile: Handle<Image>,
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct WeaponAssets {
+    #[asset(path = "weapons/machine_gun.weapon.ron")]
+    pub machine_gun: Handle<WeaponDefinition>,
+    #[asset(path = "weapons/pea_shooter.weapon.ron")]
+    pub pea_shooter: Handle<WeaponDefinition>,
+    #[asset(path = "weapons/sniper.weapon.ron")]
+    pub sniper: Handle<WeaponDefinition>,
+    #[asset(path = "weapons/bile.weapon.ron")]
+    pub bile: Handle<WeaponDefinition>,
+    #[asset(path = "weapons/boomerang.weapon.ron")]
+    pub boomerang: Handle<WeaponDefinition>,
+    #[asset(path = "weapons/heat_seeker.weapon.ron")]
+    pub heat_seeker: Handle<WeaponDefinition>,
+    #[asset(path = "weapons/tactical_nuke.weapon.ron")]
+    pub tactical_nuke: Handle<WeaponDefinition>,
+    #[asset(path = "weapons/lazer_cannon.weapon.ron")]
+    pub lazer_cannon: Handle<WeaponDefinition>,
+    #[asset(path = "weapons/air_mines.weapon.ron")]
+    pub air_mines: Handle<WeaponDefinition>,
+    #[asset(path = "weapons/flares.weapon.ron")]
+    pub flares: Handle<WeaponDefinition>,
+    #[asset(path = "weapons/leaf_blower.weapon.ron")]
+    pub leaf_blower: Handle<WeaponDefinition>,
 }
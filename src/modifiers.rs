@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use crate::power_ups::PowerUpType;
+
+/// A stat that passives are able to modify
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stat {
+    MaxHealth,
+    MoveSpeed,
+    Damage,
+    Cooldown,
+    /// size of explosions and anything else with a radius
+    Area,
+    ProjectileSpeed,
+    /// extra projectiles fired each time a weapon shoots
+    ProjectileCount,
+    /// how many enemies a projectile can hit before it is destroyed
+    Pierce,
+    /// how far away the player is able to lock onto enemies
+    TargetDistance,
+    /// chance of a projectile being a critical hit
+    CritChance,
+    /// how much the damage of a critical hit is multiplied by
+    CritMultiplier,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Modifier {
+    /// added onto the base value
+    Add(f32),
+    /// multiplies the base value after every [`Modifier::Add`] is applied
+    Multiply(f32),
+}
+
+/// Every stat modifier affecting an entity, grouped by the powerup that granted them
+#[derive(Component, Default)]
+pub struct StatModifiers(Vec<(PowerUpType, Stat, Modifier)>);
+
+impl StatModifiers {
+    /// Replaces all of the modifiers granted by `source`
+    pub fn set(
+        &mut self,
+        source: PowerUpType,
+        modifiers: impl IntoIterator<Item = (Stat, Modifier)>,
+    ) {
+        self.0.retain(|(existing, _, _)| *existing != source);
+        self.0.extend(
+            modifiers
+                .into_iter()
+                .map(|(stat, modifier)| (source, stat, modifier)),
+        );
+    }
+
+    /// Applies every modifier for `stat` to `base`. Additions are applied before multipliers
+    pub fn apply(&self, stat: Stat, base: f32) -> f32 {
+        let (add, multiply) = self
+            .0
+            .iter()
+            .filter(|(_, modified, _)| *modified == stat)
+            .fold(
+                (0.0, 1.0),
+                |(add, multiply), (_, _, modifier)| match modifier {
+                    Modifier::Add(amount) => (add + amount, multiply),
+                    Modifier::Multiply(amount) => (add, multiply * amount),
+                },
+            );
+        (base + add) * multiply
+    }
+}
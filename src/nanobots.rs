@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+
+use crate::{
+    health::{DamageAppliedEvent, Dead, HealEvent, Health, MaxHealth},
+    GameState,
+};
+
+pub struct NanobotsPlugin;
+
+impl Plugin for NanobotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (add_nanobot_state, interrupt_repairs, repair_health)
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// How long nanobots stop repairing after taking damage
+const SECS_PAUSED_AFTER_DAMAGE: f32 = 3.0;
+/// Health restored per second for each level of nanobots
+const HEALTH_PER_SEC_PER_LEVEL: f32 = 1.5;
+
+/// Gradually restores health up to [`MaxHealth`]
+#[derive(Component)]
+pub struct Nanobots(pub u8);
+
+impl Nanobots {
+    pub fn new(level: u8) -> Self {
+        Self(level)
+    }
+}
+
+#[derive(Component, Default)]
+pub struct NanobotState {
+    pub secs_until_repair: f32,
+    /// health that has been repaired but not yet added since health is whole numbers
+    partial_health: f32,
+}
+
+fn add_nanobot_state(
+    mut commands: Commands,
+    nanobots: Query<Entity, (With<Nanobots>, Without<NanobotState>)>,
+) {
+    for entity in nanobots.iter() {
+        commands.entity(entity).insert(NanobotState::default());
+    }
+}
+
+fn interrupt_repairs(
+    mut damage_events: EventReader<DamageAppliedEvent>,
+    mut nanobots: Query<&mut NanobotState>,
+) {
+    for damage in damage_events.read() {
+        if let Ok(mut state) = nanobots.get_mut(damage.applied_to) {
+            state.secs_until_repair = SECS_PAUSED_AFTER_DAMAGE;
+            state.partial_health = 0.0;
+        }
+    }
+}
+
+fn repair_health(
+    mut nanobots: Query<
+        (
+            Entity,
+            &Nanobots,
+            &mut NanobotState,
+            &mut Health,
+            &MaxHealth,
+        ),
+        Without<Dead>,
+    >,
+    mut heal_events: EventWriter<HealEvent>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+    for (entity, nanobots, mut state, mut health, max_health) in nanobots.iter_mut() {
+        if state.secs_until_repair > 0.0 {
+            state.secs_until_repair -= dt;
+            continue;
+        }
+        if health.0 >= max_health.0 {
+            state.partial_health = 0.0;
+            continue;
+        }
+        state.partial_health += nanobots.0 as f32 * HEALTH_PER_SEC_PER_LEVEL * dt;
+        let repaired = (state.partial_health as i32).min(max_health.0 - health.0);
+        if repaired > 0 {
+            state.partial_health -= repaired as f32;
+            health.0 += repaired;
+            heal_events.send(HealEvent {
+                applied_to: entity,
+                amount: repaired,
+            });
+        }
+    }
+}
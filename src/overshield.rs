@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{health::Dead, GameState};
+use crate::{
+    health::{DamageQueue, DamageStage, Dead},
+    GameState,
+};
 
 pub struct OvershieldPlugin;
 
@@ -8,7 +11,8 @@ impl Plugin for OvershieldPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, reset_overshield_state).add_systems(
             Update,
-            recharge_shields.run_if(in_state(GameState::Playing)),
+            (recharge_shields, absorb_damage.in_set(DamageStage::Shield))
+                .run_if(in_state(GameState::Playing)),
         );
     }
 }
@@ -63,3 +67,25 @@ fn recharge_shields(mut overshield: Query<&mut OvershieldState, Without<Dead>>,
         }
     }
 }
+
+/// Soaks up incoming damage with the shield, carrying forward whatever the shield can't absorb
+fn absorb_damage(mut queue: ResMut<DamageQueue>, mut overshield: Query<&mut OvershieldState>) {
+    for damage in queue.0.iter_mut() {
+        let Ok(mut overshield) = overshield.get_mut(damage.applied_to) else {
+            continue;
+        };
+        overshield.secs_until_recharge = 2.0;
+        let starting_shield = overshield.current_overshield;
+        let absorbed = (damage.amount.round() as i32).min(overshield.current_overshield.max(0));
+        overshield.current_overshield -= absorbed;
+        damage.amount = (damage.amount - absorbed as f32).max(0.0);
+        info!(
+            "Shield damage {:?}->{:?} Shield: {}-{}={}",
+            damage.damaged_by,
+            damage.applied_to,
+            starting_shield,
+            absorbed,
+            overshield.current_overshield
+        );
+    }
+}
@@ -1,6 +1,12 @@
 use bevy::{input::keyboard::NativeKeyCode, prelude::*};
+use enum_iterator::{first, Sequence};
 
-use crate::GameState;
+use crate::{
+    player::Player,
+    power_ups::{PowerUpType, Powerups},
+    targeting::TargetingMode,
+    GameState,
+};
 
 pub struct PausePlugin;
 
@@ -12,14 +18,31 @@ impl Plugin for PausePlugin {
         app.add_systems(Update, pause_menu_key.run_if(in_state(GameState::Playing)))
             .add_systems(
                 Update,
-                (unpause_menu_key, handle_main_menu_clicks).run_if(in_state(GameState::Paused)),
+                (
+                    unpause_menu_key,
+                    handle_main_menu_clicks,
+                    handle_targeting_clicks,
+                )
+                    .run_if(in_state(GameState::Paused)),
             )
             .add_systems(OnEnter(GameState::Paused), add_pause_menu)
             .add_systems(OnExit(GameState::Paused), cleanup_pause_menu);
     }
 }
 
-fn add_pause_menu(mut commands: Commands) {
+fn add_pause_menu(mut commands: Commands, player: Query<&Powerups, With<Player>>) {
+    let weapons: Vec<_> = player
+        .get_single()
+        .map(|powerups| {
+            powerups
+                .0
+                .iter()
+                .flatten()
+                .filter(|powerup| powerup.power.has_targeting())
+                .copied()
+                .collect()
+        })
+        .unwrap_or_default();
     commands
         .spawn((
             NodeBundle {
@@ -44,6 +67,25 @@ fn add_pause_menu(mut commands: Commands) {
                     ..Default::default()
                 },
             ));
+            for weapon in weapons {
+                children
+                    .spawn((
+                        ButtonBundle {
+                            background_color: BackgroundColor(Color::DARK_GREEN),
+                            ..Default::default()
+                        },
+                        TargetingButton(weapon.power),
+                    ))
+                    .with_children(|c| {
+                        c.spawn(TextBundle::from_section(
+                            targeting_label(weapon.power, weapon.targeting),
+                            TextStyle {
+                                font_size: 30.0,
+                                ..Default::default()
+                            },
+                        ));
+                    });
+            }
             children
                 .spawn((
                     ButtonBundle {
@@ -79,6 +121,51 @@ fn handle_main_menu_clicks(
     }
 }
 
+/// Cycles the [`TargetingMode`] of a weapon each time it is clicked
+#[derive(Component)]
+struct TargetingButton(PowerUpType);
+
+fn targeting_label(power: PowerUpType, targeting: Option<TargetingMode>) -> String {
+    match targeting {
+        Some(mode) => format!("{power}: {mode}"),
+        None => format!("{power}: Default"),
+    }
+}
+
+fn handle_targeting_clicks(
+    interactions: Query<(&Interaction, &TargetingButton, &Children), Changed<Interaction>>,
+    mut texts: Query<&mut Text>,
+    mut player: Query<&mut Powerups, With<Player>>,
+) {
+    for (interaction, TargetingButton(power), children) in interactions.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok(mut powerups) = player.get_single_mut() else {
+            continue;
+        };
+        let Some(powerup) = powerups
+            .0
+            .iter_mut()
+            .flatten()
+            .find(|powerup| powerup.power == *power)
+        else {
+            continue;
+        };
+        // goes through every mode and then back to the weapon's default
+        powerup.targeting = match powerup.targeting {
+            Some(mode) => mode.next(),
+            None => first::<TargetingMode>(),
+        };
+        let label = targeting_label(*power, powerup.targeting);
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = label.clone();
+            }
+        }
+    }
+}
+
 fn cleanup_pause_menu(mut commands: Commands, pause_menu: Query<Entity, With<PauseMenu>>) {
     for entity in pause_menu.iter() {
         commands.entity(entity).despawn_recursive();
@@ -1,9 +1,11 @@
 use crate::actions::Actions;
-use crate::health::{DeadTexture, DespawnTimer, Health, MaxHealth};
+use crate::enemy::Targetable;
+use crate::health::{DeadTexture, DespawnTimer, Health, Invulnerability, MaxHealth};
 use crate::leveling::{Level, Xp};
 use crate::loading::TextureAssets;
+use crate::modifiers::{Stat, StatModifiers};
 use crate::power_ups::Powerups;
-use crate::weapon::{Friendly, Target, TargetVector, Velocity, Weapon};
+use crate::weapon::{Friendly, Target, TargetDistance, TargetVector, Velocity};
 use crate::{GameState, GameSystems};
 use bevy::prelude::*;
 
@@ -12,6 +14,16 @@ pub struct PlayerPlugin;
 #[derive(Component)]
 pub struct Player;
 
+const BASE_MAX_HEALTH: i32 = 100;
+/// How long the player can't be hurt again after taking damage
+const INVULNERABILITY_SECONDS: f32 = 0.5;
+/// How quickly the player speeds up while moving
+const BASE_ACCELERATION: f32 = 1000.0;
+const BASE_MAX_SPEED: f32 = 200.0;
+/// How far away the player can lock onto enemies without any upgrades
+const BASE_TARGET_DISTANCE: f32 = 400.0;
+const TARGET_DISTANCE_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.15);
+
 /// This plugin handles player related stuff like movement
 /// Player logic is only active during the State `GameState::Playing`
 impl Plugin for PlayerPlugin {
@@ -31,6 +43,9 @@ impl Plugin for PlayerPlugin {
                     .in_set(GameSystems::PreMovement)
                     .after(GameSystems::Input),
                 dead_player,
+                apply_max_health_modifiers,
+                apply_target_distance_modifiers,
+                draw_target_distance,
             )
                 .run_if(in_state(GameState::Playing)),
         );
@@ -50,14 +65,16 @@ fn spawn_player(
                     .with_scale(Vec3::new(0.5, 0.5, 0.5)),
                 ..Default::default()
             },
-            Weapon {
-                cooldown: 0.1,
-                cooldown_left: 1.0,
-            },
             Target(None),
             TargetVector(None),
-            MaxHealth(100),
-            Health(100),
+            Targetable {
+                priority: 0,
+                range: None,
+            },
+            MaxHealth(BASE_MAX_HEALTH),
+            Health(BASE_MAX_HEALTH),
+            StatModifiers::default(),
+            TargetDistance(BASE_TARGET_DISTANCE),
             Velocity(Vec2::new(0.0, 100.0)),
             Friendly,
             Powerups::default(),
@@ -66,7 +83,7 @@ fn spawn_player(
             Xp(0),
             Level(1),
         ))
-        .insert(Player);
+        .insert((Player, Invulnerability::new(INVULNERABILITY_SECONDS)));
     next_state.set(GameState::Chooser);
 }
 
@@ -79,19 +96,61 @@ fn despawn_player(mut commands: Commands, player: Query<Entity, With<Player>>) {
 fn move_player(
     time: Res<Time>,
     actions: Res<Actions>,
-    mut player_query: Query<&mut Velocity, (With<Player>, Without<DespawnTimer>)>,
+    mut player_query: Query<(&mut Velocity, &StatModifiers), (With<Player>, Without<DespawnTimer>)>,
 ) {
     if actions.player_movement.is_none() {
         return;
     }
-    let speed = 1000.;
-    let movement = Vec2::new(
-        actions.player_movement.unwrap().x * speed * time.delta_seconds(),
-        actions.player_movement.unwrap().y * speed * time.delta_seconds(),
-    );
-    for mut player_velocity in &mut player_query {
+    for (mut player_velocity, modifiers) in &mut player_query {
+        let speed = modifiers.apply(Stat::MoveSpeed, BASE_ACCELERATION);
+        let movement = Vec2::new(
+            actions.player_movement.unwrap().x * speed * time.delta_seconds(),
+            actions.player_movement.unwrap().y * speed * time.delta_seconds(),
+        );
         player_velocity.0 += movement;
-        player_velocity.0 = player_velocity.0.clamp_length_max(200.0);
+        player_velocity.0 = player_velocity
+            .0
+            .clamp_length_max(modifiers.apply(Stat::MoveSpeed, BASE_MAX_SPEED));
+    }
+}
+
+/// Recalculates max health when modifiers change. Any extra max health is also added to current health
+fn apply_max_health_modifiers(
+    mut players: Query<(&StatModifiers, &mut MaxHealth, &mut Health), Changed<StatModifiers>>,
+) {
+    for (modifiers, mut max_health, mut health) in players.iter_mut() {
+        let new_max = modifiers
+            .apply(Stat::MaxHealth, BASE_MAX_HEALTH as f32)
+            .round() as i32;
+        if new_max != max_health.0 {
+            let gained = (new_max - max_health.0).max(0);
+            max_health.0 = new_max;
+            health.0 = (health.0 + gained).min(new_max);
+        }
+    }
+}
+
+fn apply_target_distance_modifiers(
+    mut players: Query<(&StatModifiers, &mut TargetDistance), Changed<StatModifiers>>,
+) {
+    for (modifiers, mut target_distance) in players.iter_mut() {
+        target_distance.0 = modifiers.apply(Stat::TargetDistance, BASE_TARGET_DISTANCE);
+    }
+}
+
+/// Outlines how far the player can target once it has been upgraded
+fn draw_target_distance(
+    mut gizmos: Gizmos,
+    players: Query<(&Transform, &TargetDistance), (With<Player>, Without<DespawnTimer>)>,
+) {
+    for (transform, target_distance) in players.iter() {
+        if target_distance.0 > BASE_TARGET_DISTANCE {
+            gizmos.circle_2d(
+                transform.translation.truncate(),
+                target_distance.0,
+                TARGET_DISTANCE_COLOR,
+            );
+        }
     }
 }
 
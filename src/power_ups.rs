@@ -4,13 +4,26 @@ use enum_iterator::{all, Sequence};
 use rand::{seq::SliceRandom, Rng};
 
 use crate::{
+    air_mines::AirMines,
+    boomerang::Boomerang,
+    collider::{BoxCollider, Collider},
+    drones::Drones,
     enemy::Enemy,
+    flares::Flares,
     health::DeathEvent,
+    heat_seeker::HeatSeeker,
+    lazer_cannon::LazerCannon,
     loading::TextureAssets,
+    modifiers::{Modifier, Stat, StatModifiers},
+    nanobots::Nanobots,
     overshield::Overshield,
     player::Player,
-    weapon::{Bile, Coord2D, MachineGun, PeaShooter, Sniper, SpecialMunitions},
-    GameState,
+    spatial_index::{SpatialIndex, SpatialIndexed},
+    squadron::Squadron,
+    tactical_nuke::TacticalNuke,
+    targeting::TargetingMode,
+    weapon::{Bile, Coord2D, LeafBlower, MachineGun, PeaShooter, Sniper},
+    GameState, GameSystems,
 };
 
 pub struct PowerupPlugin;
@@ -19,7 +32,12 @@ impl Plugin for PowerupPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (spawn_pickup, player_pickup, powerup_manager).run_if(in_state(GameState::Playing)),
+            (
+                spawn_pickup,
+                player_pickup.in_set(GameSystems::Collision),
+                powerup_manager,
+            )
+                .run_if(in_state(GameState::Playing)),
         )
         .add_systems(OnEnter(GameState::Menu), cleanup_pickups)
         .add_systems(OnEnter(GameState::Chooser), add_choice_menu)
@@ -50,6 +68,8 @@ fn spawn_pickup(
                         ..Default::default()
                     },
                     Pickup,
+                    SpatialIndexed,
+                    BoxCollider,
                 ));
             }
         }
@@ -70,17 +90,19 @@ struct Choice(PowerUpType);
 
 fn player_pickup(
     mut commands: Commands,
-    players: Query<&Transform, With<Player>>,
-    pickups: Query<(Entity, &Transform), With<Pickup>>,
+    players: Query<(&Transform, &Collider), With<Player>>,
+    pickups: Query<(&Transform, &Collider), With<Pickup>>,
+    index: Res<SpatialIndex>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    for player in players.iter() {
-        for (pickup, pickup_location) in pickups.iter() {
-            // check if they overlap
-            if (*Coord2D::from(player.translation) - *Coord2D::from(pickup_location.translation))
-                .length_squared()
-                < 1000.0
-            {
+    for (player, player_collider) in players.iter() {
+        let position = *Coord2D::from(player.translation);
+        let reach = player_collider.bounding_radius(player);
+        for pickup in index.candidates(position, reach) {
+            let Ok((pickup_location, pickup_collider)) = pickups.get(pickup) else {
+                continue;
+            };
+            if player_collider.overlaps(player, pickup_collider, pickup_location) {
                 commands.entity(pickup).despawn();
                 next_state.set(GameState::Chooser);
             }
@@ -107,20 +129,46 @@ pub struct Powerups(pub [Option<Powerup>; 6]);
 
 fn powerup_manager(
     mut commands: Commands,
-    powerups: Query<(Entity, &Powerups), Changed<Powerups>>,
+    mut powerups: Query<(Entity, &Powerups, Option<&mut StatModifiers>), Changed<Powerups>>,
 ) {
-    for (entity, powerups) in &powerups {
+    for (entity, powerups, mut modifiers) in &mut powerups {
         for powerup in powerups.0.iter().flatten() {
             let level = powerup.level;
+            let targeting = powerup.targeting;
             let mut entity = commands.entity(entity);
             match powerup.power {
-                PowerUpType::MachineGun => entity.insert(MachineGun::new(level)),
-                PowerUpType::PeaShooter => entity.insert(PeaShooter::new(level)),
-                PowerUpType::Sniper => entity.insert(Sniper::new(level)),
-                PowerUpType::SpecialMunitions => entity.insert(SpecialMunitions::new(level)),
-                PowerUpType::Overshield => entity.insert(Overshield::new(level)),
-                PowerUpType::Bile => entity.insert(Bile::new(level)),
-            };
+                PowerUpType::MachineGun => MachineGun::equip(&mut entity, level, targeting),
+                PowerUpType::PeaShooter => PeaShooter::equip(&mut entity, level, targeting),
+                PowerUpType::Sniper => Sniper::equip(&mut entity, level, targeting),
+                PowerUpType::Overshield => {
+                    entity.insert(Overshield::new(level));
+                }
+                PowerUpType::Bile => Bile::equip(&mut entity, level, targeting),
+                PowerUpType::Boomerang => Boomerang::equip(&mut entity, level, targeting),
+                PowerUpType::HeatSeeker => HeatSeeker::equip(&mut entity, level, targeting),
+                PowerUpType::TacticalNuke => TacticalNuke::equip(&mut entity, level, targeting),
+                PowerUpType::LazerCannon => LazerCannon::equip(&mut entity, level, targeting),
+                PowerUpType::LeafBlower => LeafBlower::equip(&mut entity, level, targeting),
+                PowerUpType::AirMines => AirMines::equip(&mut entity, level, targeting),
+                PowerUpType::Drones => {
+                    entity.insert(Drones::new(level));
+                }
+                PowerUpType::Nanobots => {
+                    entity.insert(Nanobots::new(level));
+                }
+                PowerUpType::Flares => Flares::equip(&mut entity, level, targeting),
+                PowerUpType::Squadron => Squadron::equip(&mut entity, level),
+                PowerUpType::SpecialMunitions
+                | PowerUpType::Armor
+                | PowerUpType::EnergySoda
+                | PowerUpType::Piercing
+                | PowerUpType::ExtraProjectile
+                | PowerUpType::SatelliteSupport
+                | PowerUpType::TargetingComputer => {}
+            }
+            if let Some(modifiers) = modifiers.as_deref_mut() {
+                modifiers.set(powerup.power, powerup.power.stat_modifiers(level));
+            }
         }
     }
 }
@@ -153,6 +201,7 @@ impl Powerups {
                     *item = Some(Powerup {
                         power: power_type,
                         level: 1,
+                        targeting: None,
                     });
                     break;
                 }
@@ -226,7 +275,7 @@ fn add_choice_menu(
                 ))
                 .with_children(|c| {
                     c.spawn(TextBundle::from_section(
-                        format!("{:?}", choice),
+                        choice.to_string(),
                         TextStyle {
                             font_size: 60.0,
                             ..Default::default()
@@ -243,9 +292,12 @@ fn remove_choice_menu(mut commands: Commands, menu: Query<Entity, With<ChoiceMen
     }
 }
 
+#[derive(Clone, Copy)]
 pub struct Powerup {
     pub power: PowerUpType,
     pub level: u8,
+    /// overrides how the weapon picks its target
+    pub targeting: Option<TargetingMode>,
 }
 
 /// [`PowerUpType`] is just an enumeration of each type of powerup that a ship can have- players and enemies share these power ups
@@ -259,37 +311,126 @@ pub enum PowerUpType {
     Sniper,
     /// Shoots a spray of green bile
     Bile,
-    // /// Passes through multiple enemies and curves back to you
-    // Boomerrang,
-    // /// Follows the heat signature of an enemy and explodes
-    // HeatSeeker,
-    // /// Drops a giant nuke directly ontop of you
-    // TacticalNuke,
-    // /// Fires a beam directly in front of you
-    // LazerCannon,
-    // /// Sprays small leaves that enemies slip on
-    // LeafBlower,
-    // /// Stationary floating mines that enemies run over
-    // AirMines,
-    // /// Companion drones that fire pea shooters separately
-    // Drones,
-    // /// Heals self gradually
-    // Nanobots,
-    // /// prevents the enemy from targeting you while active
-    // Flares,
+    /// Passes through multiple enemies and curves back to you
+    Boomerang,
+    /// Follows the heat signature of an enemy and explodes
+    HeatSeeker,
+    /// Drops a giant nuke directly ontop of you
+    TacticalNuke,
+    /// Fires a beam directly in front of you
+    LazerCannon,
+    /// Sprays small leaves that enemies slip on
+    LeafBlower,
+    /// Stationary floating mines that enemies run over
+    AirMines,
+    /// Companion drones that fire pea shooters separately
+    Drones,
+    /// Heals self gradually
+    Nanobots,
+    /// prevents the enemy from targeting you while active
+    Flares,
     /// Adds extra health ontop of your current health
     Overshield,
     /// Increases the damage of all weapons
     SpecialMunitions,
-    // Increases the number of enemies that projectiles will pass through
-    // /// Adds total health
-    // Armor,
-    // /// Duplicates yourself and copies all weapons
-    // Squadron,
-    // /// Increases the number of projectiles
-    // ExtraProjectile,
-    // /// Increases targeting distance
-    // SatelliteSupport,
-    // /// Increases movement speed
-    // EnergySoda,
+    /// Increases the number of enemies that projectiles will pass through
+    Piercing,
+    /// Adds total health
+    Armor,
+    /// Duplicates yourself and copies all weapons
+    Squadron,
+    /// Increases the number of projectiles
+    ExtraProjectile,
+    /// Increases targeting distance
+    SatelliteSupport,
+    /// Increases movement speed
+    EnergySoda,
+    /// Increases the chance and damage of critical hits
+    TargetingComputer,
+}
+
+impl std::fmt::Display for PowerUpType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PowerUpType::PeaShooter => "Pea Shooter",
+            PowerUpType::MachineGun => "Machine Gun",
+            PowerUpType::Sniper => "Sniper",
+            PowerUpType::Bile => "Bile",
+            PowerUpType::Boomerang => "Boomerang",
+            PowerUpType::HeatSeeker => "Heat Seeker",
+            PowerUpType::TacticalNuke => "Tactical Nuke",
+            PowerUpType::LazerCannon => "Lazer Cannon",
+            PowerUpType::LeafBlower => "Leaf Blower",
+            PowerUpType::AirMines => "Air Mines",
+            PowerUpType::Drones => "Drones",
+            PowerUpType::Nanobots => "Nanobots",
+            PowerUpType::Flares => "Flares",
+            PowerUpType::Overshield => "Overshield",
+            PowerUpType::SpecialMunitions => "Special Munitions",
+            PowerUpType::Piercing => "Piercing",
+            PowerUpType::Armor => "Armor",
+            PowerUpType::Squadron => "Squadron",
+            PowerUpType::ExtraProjectile => "Extra Projectile",
+            PowerUpType::SatelliteSupport => "Satellite Support",
+            PowerUpType::EnergySoda => "Energy Soda",
+            PowerUpType::TargetingComputer => "Targeting Computer",
+        };
+        f.write_str(name)
+    }
+}
+
+impl PowerUpType {
+    /// Weapons are copied by wingmen, passives only apply to whoever picked them up
+    pub fn is_weapon(&self) -> bool {
+        matches!(
+            self,
+            PowerUpType::PeaShooter
+                | PowerUpType::MachineGun
+                | PowerUpType::Sniper
+                | PowerUpType::Bile
+                | PowerUpType::Boomerang
+                | PowerUpType::HeatSeeker
+                | PowerUpType::TacticalNuke
+                | PowerUpType::LazerCannon
+                | PowerUpType::LeafBlower
+                | PowerUpType::AirMines
+                | PowerUpType::Flares
+        )
+    }
+
+    /// Weapons that pick their own target with a [`TargetingMode`]
+    pub fn has_targeting(&self) -> bool {
+        matches!(
+            self,
+            PowerUpType::PeaShooter
+                | PowerUpType::MachineGun
+                | PowerUpType::Sniper
+                | PowerUpType::Bile
+                | PowerUpType::Boomerang
+                | PowerUpType::HeatSeeker
+                | PowerUpType::LeafBlower
+        )
+    }
+
+    /// The stat modifiers a passive powerup grants at `level`
+    fn stat_modifiers(&self, level: u8) -> Vec<(Stat, Modifier)> {
+        let level = level as f32;
+        match self {
+            PowerUpType::SpecialMunitions => vec![(Stat::Damage, Modifier::Multiply(level))],
+            PowerUpType::Armor => vec![(Stat::MaxHealth, Modifier::Add(25.0 * level))],
+            PowerUpType::EnergySoda => {
+                vec![(Stat::MoveSpeed, Modifier::Multiply(1.0 + 0.15 * level))]
+            }
+            PowerUpType::Piercing => vec![(Stat::Pierce, Modifier::Add(level))],
+            PowerUpType::ExtraProjectile => vec![(Stat::ProjectileCount, Modifier::Add(level))],
+            PowerUpType::SatelliteSupport => {
+                vec![(Stat::TargetDistance, Modifier::Multiply(1.0 + 0.25 * level))]
+            }
+            PowerUpType::TargetingComputer => vec![
+                (Stat::CritChance, Modifier::Add(0.05 * level)),
+                (Stat::CritMultiplier, Modifier::Add(0.25 * level)),
+            ],
+            _ => vec![],
+        }
+    }
 }
@@ -0,0 +1,203 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{collider::Collider, health::Health, weapon::Coord2D, GameState, GameSystems};
+
+pub struct SpatialIndexPlugin;
+
+impl Plugin for SpatialIndexPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialIndex>().add_systems(
+            Update,
+            rebuild_spatial_index
+                .after(GameSystems::Movement)
+                .before(GameSystems::Collision)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Width and height of each cell in the grid
+const CELL_SIZE: f32 = 64.0;
+/// Fixed update systems see the index from the end of the last frame, so lookups search a little further than asked
+const STALE_MARGIN: f32 = 16.0;
+
+/// Entities without [`Health`] that should still be found by [`SpatialIndex`] lookups
+#[derive(Component, Default)]
+pub struct SpatialIndexed;
+
+/// A uniform grid of everything with [`Health`] or [`SpatialIndexed`], rebuilt every frame after movement.
+/// Lookups only return candidates, callers still check the exact distance using the live [`Transform`]
+#[derive(Resource, Default)]
+pub struct SpatialIndex {
+    cells: HashMap<IVec2, Vec<Entity>>,
+    /// smallest and largest occupied cell, so searches know when to stop
+    bounds: Option<(IVec2, IVec2)>,
+    /// bounding radius of the biggest [`Collider`], anything this close to a cell can overlap it
+    largest_radius: f32,
+}
+
+impl SpatialIndex {
+    fn cell(position: Vec2) -> IVec2 {
+        (position / CELL_SIZE).floor().as_ivec2()
+    }
+
+    fn insert(&mut self, entity: Entity, position: Vec2) {
+        let cell = Self::cell(position);
+        self.cells.entry(cell).or_default().push(entity);
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(cell), max.max(cell)),
+            None => (cell, cell),
+        });
+    }
+
+    fn clear(&mut self) {
+        // cells that were used last tick are likely to be used again, so only drop empty ones
+        self.cells.retain(|_, entities| {
+            let occupied = !entities.is_empty();
+            entities.clear();
+            occupied
+        });
+        self.bounds = None;
+        self.largest_radius = 0.0;
+    }
+
+    /// Every entity whose collider could be touching the circle of `radius` around `center`
+    pub fn candidates(&self, center: Vec2, radius: f32) -> impl Iterator<Item = Entity> + '_ {
+        let reach = Vec2::splat(radius + self.largest_radius + STALE_MARGIN);
+        let (mut min, mut max) = (Self::cell(center - reach), Self::cell(center + reach));
+        if let Some((low, high)) = self.bounds {
+            min = min.max(low);
+            max = max.min(high);
+        } else {
+            // nothing is indexed, so make the range empty
+            max = min - IVec2::ONE;
+        }
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+
+    /// Finds the closest entity to `center` within `max_distance`.
+    /// `distance_to` returns the live distance to an entity, or `None` to skip it
+    pub fn nearest(
+        &self,
+        center: Vec2,
+        max_distance: f32,
+        mut distance_to: impl FnMut(Entity) -> Option<f32>,
+    ) -> Option<(Entity, f32)> {
+        let (low, high) = self.bounds?;
+        let origin = Self::cell(center);
+        // the furthest ring that could still have something in it
+        let mut last_ring = (origin - low).max(high - origin).max_element().max(0);
+        if max_distance.is_finite() {
+            last_ring = last_ring.min(((max_distance + STALE_MARGIN) / CELL_SIZE).ceil() as i32);
+        }
+        let mut best: Option<(Entity, f32)> = None;
+        for ring in 0..=last_ring {
+            for cell in ring_cells(origin, ring) {
+                let Some(entities) = self.cells.get(&cell) else {
+                    continue;
+                };
+                for &entity in entities {
+                    let Some(distance) = distance_to(entity) else {
+                        continue;
+                    };
+                    if distance < max_distance && best.is_none_or(|(_, d)| distance < d) {
+                        best = Some((entity, distance));
+                    }
+                }
+            }
+            // anything in the next ring is at least this far away
+            let next_ring_distance = ring as f32 * CELL_SIZE - STALE_MARGIN;
+            if best.is_some_and(|(_, distance)| distance < next_ring_distance) {
+                break;
+            }
+        }
+        best
+    }
+}
+
+/// The cells that are exactly `ring` cells away from `origin`
+fn ring_cells(origin: IVec2, ring: i32) -> impl Iterator<Item = IVec2> {
+    (-ring..=ring).flat_map(move |x| {
+        let on_edge = x.abs() == ring;
+        // cells in the middle columns only sit on the top and bottom of the ring
+        let ys: Vec<i32> = if on_edge {
+            (-ring..=ring).collect()
+        } else {
+            vec![-ring, ring]
+        };
+        ys.into_iter().map(move |y| origin + IVec2::new(x, y))
+    })
+}
+
+fn rebuild_spatial_index(
+    mut index: ResMut<SpatialIndex>,
+    indexed: Query<
+        (Entity, &Transform, Option<&Collider>),
+        Or<(With<Health>, With<SpatialIndexed>)>,
+    >,
+) {
+    index.clear();
+    for (entity, transform, collider) in indexed.iter() {
+        index.insert(entity, *Coord2D::from(transform.translation));
+        if let Some(collider) = collider {
+            index.largest_radius = index
+                .largest_radius
+                .max(collider.bounding_radius(transform));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Indexes each position as its own entity and finds the nearest to `center`,
+    /// returning its position along with every entity that had its distance checked
+    fn nearest(positions: &[Vec2], center: Vec2, max_distance: f32) -> (Option<Vec2>, Vec<u32>) {
+        let mut index = SpatialIndex::default();
+        for (id, position) in positions.iter().enumerate() {
+            index.insert(Entity::from_raw(id as u32), *position);
+        }
+        let mut checked = Vec::new();
+        let found = index.nearest(center, max_distance, |entity| {
+            checked.push(entity.index());
+            Some(positions[entity.index() as usize].distance(center))
+        });
+        (found.map(|(e, _)| positions[e.index() as usize]), checked)
+    }
+
+    #[test]
+    fn nearest_looks_past_the_first_hit_near_a_cell_boundary() {
+        // the center sits at the right edge of its cell, so the closest entity is in the next ring
+        let same_cell = Vec2::new(10.0, 0.0);
+        let next_cell = Vec2::new(70.0, 0.0);
+        let (found, _) = nearest(&[same_cell, next_cell], Vec2::new(60.0, 0.0), f32::INFINITY);
+        assert_eq!(found, Some(next_cell));
+    }
+
+    #[test]
+    fn nearest_stops_once_no_further_ring_can_be_closer() {
+        let close = Vec2::new(32.0, 40.0);
+        let far = Vec2::new(32.0 + CELL_SIZE * 3.0, 32.0);
+        let (found, checked) = nearest(&[close, far], Vec2::new(32.0, 32.0), f32::INFINITY);
+        assert_eq!(found, Some(close));
+        assert_eq!(checked, vec![0]);
+    }
+
+    #[test]
+    fn nearest_ignores_anything_past_max_distance() {
+        let (found, _) = nearest(&[Vec2::new(100.0, 0.0)], Vec2::ZERO, 50.0);
+        assert_eq!(found, None);
+    }
+
+    #[test]
+    fn nearest_on_an_empty_index_finds_nothing() {
+        let (found, checked) = nearest(&[], Vec2::ZERO, f32::INFINITY);
+        assert_eq!(found, None);
+        assert!(checked.is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+use bevy::{ecs::system::EntityCommands, prelude::*};
+
+use crate::{
+    enemy::Targetable,
+    health::{Dead, DeadTexture, Health, MaxHealth},
+    loading::TextureAssets,
+    power_ups::Powerups,
+    weapon::{Coord2D, Friendly, Target, TargetVector, Velocity},
+    GameState, GameSystems,
+};
+
+pub struct SquadronPlugin;
+
+impl Plugin for SquadronPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                sync_squadron,
+                mirror_loadout,
+                fly_in_formation.after(GameSystems::Movement),
+            )
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(OnEnter(GameState::Menu), cleanup_wingmen);
+    }
+}
+
+const WINGMAN_HEALTH: i32 = 50;
+/// Distance between each row of the formation
+const FORMATION_SPACING: Vec2 = Vec2::new(60.0, 50.0);
+/// How quickly wingmen catch up to their place in the formation
+const FORMATION_STIFFNESS: f32 = 8.0;
+
+/// Gives the entity a wingman for each level that copies all of its weapons.
+/// Wingmen that are shot down are not replaced
+#[derive(Component)]
+pub struct Squadron {
+    size: u8,
+    /// how many wingmen have been spawned, including the ones that were lost
+    spawned: u8,
+}
+
+impl Squadron {
+    /// Grows the squadron to `level` wingmen without replacing any that were lost
+    pub fn equip(entity: &mut EntityCommands, level: u8) {
+        entity.add(move |mut entity: EntityWorldMut| {
+            if let Some(mut squadron) = entity.get_mut::<Squadron>() {
+                squadron.size = level;
+            } else {
+                entity.insert(Squadron {
+                    size: level,
+                    spawned: 0,
+                });
+            }
+        });
+    }
+}
+
+#[derive(Component)]
+struct Wingman {
+    leader: Entity,
+    /// position of this wingman in the formation
+    slot: u8,
+}
+
+/// Only the weapons from `powerups`, so wingmen don't copy passives or their own squadron
+fn weapon_loadout(powerups: &Powerups) -> Powerups {
+    Powerups(
+        powerups
+            .0
+            .map(|powerup| powerup.filter(|powerup| powerup.power.is_weapon())),
+    )
+}
+
+fn sync_squadron(
+    mut commands: Commands,
+    mut leaders: Query<
+        (Entity, &mut Squadron, &Powerups, &Transform, &Velocity),
+        Changed<Squadron>,
+    >,
+    textures: Res<TextureAssets>,
+) {
+    for (leader, mut squadron, powerups, transform, velocity) in leaders.iter_mut() {
+        for slot in squadron.spawned..squadron.size {
+            commands.spawn((
+                SpriteBundle {
+                    texture: textures.player.clone(),
+                    transform: *transform,
+                    ..Default::default()
+                },
+                Wingman { leader, slot },
+                weapon_loadout(powerups),
+                Health(WINGMAN_HEALTH),
+                MaxHealth(WINGMAN_HEALTH),
+                DeadTexture(textures.player_dead.clone()),
+                Targetable {
+                    priority: 0,
+                    range: None,
+                },
+                Target(None),
+                TargetVector(None),
+                // weapons like flares and air mines fire based on how the ship is moving
+                Velocity(velocity.0),
+                Friendly,
+            ));
+        }
+        squadron.spawned = squadron.spawned.max(squadron.size);
+    }
+}
+
+/// Copies the leader's weapons onto its wingmen whenever the leader picks up a powerup
+fn mirror_loadout(
+    leaders: Query<&Powerups, (Changed<Powerups>, With<Squadron>)>,
+    mut wingmen: Query<(&Wingman, &mut Powerups), (Without<Squadron>, Without<Dead>)>,
+) {
+    for (wingman, mut powerups) in wingmen.iter_mut() {
+        if let Ok(leader) = leaders.get(wingman.leader) {
+            *powerups = weapon_loadout(leader);
+        }
+    }
+}
+
+/// Keeps wingmen in a V behind their leader, sharing the leader's target and velocity
+fn fly_in_formation(
+    mut commands: Commands,
+    mut wingmen: Query<
+        (Entity, &Wingman, &mut Transform, &mut Target, &mut Velocity),
+        Without<Dead>,
+    >,
+    leaders: Query<(&Transform, &Target, &Velocity), (With<Squadron>, Without<Wingman>)>,
+    time: Res<Time>,
+) {
+    let catch_up = 1.0 - (-FORMATION_STIFFNESS * time.delta_seconds()).exp();
+    for (entity, wingman, mut transform, mut target, mut velocity) in wingmen.iter_mut() {
+        let Ok((leader, leader_target, leader_velocity)) = leaders.get(wingman.leader) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let row = (wingman.slot / 2 + 1) as f32;
+        let side = if wingman.slot % 2 == 0 { -1.0 } else { 1.0 };
+        let offset = leader.rotation
+            * Vec3::new(
+                side * row * FORMATION_SPACING.x,
+                -row * FORMATION_SPACING.y,
+                0.0,
+            );
+        let position = *Coord2D::from(leader.translation + offset);
+        let current = *Coord2D::from(transform.translation);
+        transform.translation = current
+            .lerp(position, catch_up)
+            .extend(leader.translation.z);
+        transform.rotation = leader.rotation;
+        transform.scale = leader.scale;
+        target.0 = leader_target.0;
+        velocity.0 = leader_velocity.0;
+    }
+}
+
+fn cleanup_wingmen(mut commands: Commands, wingmen: Query<Entity, With<Wingman>>) {
+    for wingman in wingmen.iter() {
+        commands.entity(wingman).despawn();
+    }
+}
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::{
     enemy::Enemy,
-    health::{DamageEvent, DeathEvent},
+    health::{DamageAppliedEvent, DeathEvent, HealEvent},
     GameState, GameSystems,
 };
 pub struct StatsPlugin;
@@ -10,13 +10,20 @@ pub struct StatsPlugin;
 impl Plugin for StatsPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(TotalDamageDone(0))
+            .insert_resource(TotalHealingDone(0))
+            .insert_resource(TotalCriticalHits(0))
             // .insert_resource(TotalHealthLost(0))
             .insert_resource(TotalEnemiesKilled(0))
             .insert_resource(TotalBulletsFired(0))
             .insert_resource(EnemiesStillAlive(0))
             .add_systems(
                 FixedUpdate,
-                (count_enemies, collect_damage_done, count_deaths)
+                (
+                    count_enemies,
+                    collect_damage_done,
+                    collect_healing_done,
+                    count_deaths,
+                )
                     .after(GameSystems::Collision)
                     .run_if(in_state(GameState::Playing)),
             )
@@ -26,11 +33,15 @@ impl Plugin for StatsPlugin {
 
 fn reset_stats(
     mut total_damage_done: ResMut<TotalDamageDone>,
+    mut total_healing_done: ResMut<TotalHealingDone>,
+    mut critical_hits: ResMut<TotalCriticalHits>,
     mut enemies_killed: ResMut<TotalEnemiesKilled>,
     mut enemies_alive: ResMut<EnemiesStillAlive>,
     mut bullets_fired: ResMut<TotalBulletsFired>,
 ) {
     total_damage_done.0 = 0;
+    total_healing_done.0 = 0;
+    critical_hits.0 = 0;
     enemies_alive.0 = 0;
     enemies_killed.0 = 0;
     bullets_fired.0 = 0;
@@ -39,12 +50,31 @@ fn reset_stats(
 #[derive(Resource)]
 pub struct TotalDamageDone(pub u32);
 
+#[derive(Resource)]
+pub struct TotalCriticalHits(pub u32);
+
 fn collect_damage_done(
-    mut damage_events: EventReader<DamageEvent>,
+    mut damage_events: EventReader<DamageAppliedEvent>,
     mut damage_done: ResMut<TotalDamageDone>,
+    mut critical_hits: ResMut<TotalCriticalHits>,
 ) {
     for damage in damage_events.read() {
         damage_done.0 += damage.amount as u32;
+        if damage.critical {
+            critical_hits.0 += 1;
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct TotalHealingDone(pub u32);
+
+fn collect_healing_done(
+    mut heal_events: EventReader<HealEvent>,
+    mut healing_done: ResMut<TotalHealingDone>,
+) {
+    for heal in heal_events.read() {
+        healing_done.0 += heal.amount as u32;
     }
 }
 
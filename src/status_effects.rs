@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    health::{DamageEvent, DamageQueue, DamageStage, DamageType, Dead, Health},
+    GameState, GameSystems,
+};
+
+pub struct StatusEffectsPlugin;
+
+impl Plugin for StatusEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<StatusEvent>()
+            .add_systems(
+                Update,
+                (
+                    apply_status_events,
+                    tick_status_effects,
+                    tint_status_effects,
+                )
+                    .chain()
+                    .after(GameSystems::Collision)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                amplify_poisoned_damage
+                    .in_set(DamageStage::Resistance)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// How many stacks of the same kind of effect an entity can have at once
+const MAX_STACKS: usize = 5;
+/// Fraction of top speed that stunned entities are left with.
+/// Things are never brought to a complete stop so they keep a direction to face
+const STUNNED_SPEED: f32 = 0.01;
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusKind {
+    /// `strength` is the fraction of top speed lost per stack
+    Slow,
+    /// `strength` is the [`DamageType::Fire`] damage dealt per second per stack
+    Burn,
+    /// `strength` is the extra fraction of damage taken per stack
+    Poison,
+    /// stops the entity from moving or turning
+    Stun,
+}
+
+impl StatusKind {
+    fn tint(&self) -> Color {
+        match self {
+            StatusKind::Slow => Color::rgb(0.5, 0.7, 1.0),
+            StatusKind::Burn => Color::rgb(1.0, 0.5, 0.3),
+            StatusKind::Poison => Color::rgb(0.5, 1.0, 0.4),
+            StatusKind::Stun => Color::rgb(1.0, 1.0, 0.4),
+        }
+    }
+}
+
+/// A timed effect that can be inflicted on anything with [`Health`]
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    #[serde(default)]
+    pub strength: f32,
+    /// how many seconds the effect lasts for
+    pub duration: f32,
+}
+
+/// Sent to inflict a [`StatusEffect`], alongside any [`DamageEvent`] for the same hit
+#[derive(Event, Clone, Copy, Debug)]
+pub struct StatusEvent {
+    pub applied_by: Entity,
+    pub applied_to: Entity,
+    pub effect: StatusEffect,
+}
+
+/// Projectiles with this component inflict the effect on whatever they hit
+#[derive(Component, Clone, Copy)]
+pub struct InflictsStatus(pub StatusEffect);
+
+struct Stack {
+    applied_by: Entity,
+    effect: StatusEffect,
+    remaining: f32,
+}
+
+/// Every status effect currently affecting an entity
+#[derive(Component, Default)]
+pub struct StatusEffects {
+    stacks: Vec<Stack>,
+    /// burn damage that hasn't added up to a whole point yet
+    burn_damage: f32,
+}
+
+impl StatusEffects {
+    fn stacks_of(&self, kind: StatusKind) -> impl Iterator<Item = &Stack> {
+        self.stacks
+            .iter()
+            .filter(move |stack| stack.effect.kind == kind)
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.stacks_of(StatusKind::Stun).next().is_some()
+    }
+
+    /// Multiplier for top speed. Each slow stack compounds on the last
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.is_stunned() {
+            return STUNNED_SPEED;
+        }
+        self.stacks_of(StatusKind::Slow)
+            .map(|stack| 1.0 - stack.effect.strength.clamp(0.0, 1.0))
+            .product::<f32>()
+            .max(STUNNED_SPEED)
+    }
+
+    /// Multiplier for incoming damage
+    fn damage_taken_multiplier(&self) -> f32 {
+        1.0 + self
+            .stacks_of(StatusKind::Poison)
+            .map(|stack| stack.effect.strength)
+            .sum::<f32>()
+    }
+
+    /// Adds a stack of `effect`, replacing the stack closest to wearing off once at [`MAX_STACKS`]
+    fn add(&mut self, applied_by: Entity, effect: StatusEffect) {
+        if self.stacks_of(effect.kind).count() >= MAX_STACKS {
+            if let Some(index) = self
+                .stacks
+                .iter()
+                .enumerate()
+                .filter(|(_, stack)| stack.effect.kind == effect.kind)
+                .min_by(|(_, a), (_, b)| a.remaining.total_cmp(&b.remaining))
+                .map(|(index, _)| index)
+            {
+                self.stacks.swap_remove(index);
+            }
+        }
+        self.stacks.push(Stack {
+            applied_by,
+            effect,
+            remaining: effect.duration,
+        });
+    }
+
+    /// Tint of whichever effect has the most stacks
+    fn tint(&self) -> Color {
+        [
+            StatusKind::Stun,
+            StatusKind::Burn,
+            StatusKind::Poison,
+            StatusKind::Slow,
+        ]
+        .into_iter()
+        .map(|kind| (kind, self.stacks_of(kind).count()))
+        .filter(|(_, count)| *count > 0)
+        .max_by_key(|(_, count)| *count)
+        .map(|(kind, _)| kind.tint())
+        .unwrap_or(Color::WHITE)
+    }
+}
+
+fn apply_status_events(
+    mut commands: Commands,
+    mut events: EventReader<StatusEvent>,
+    targets: Query<(), (With<Health>, Without<Dead>)>,
+) {
+    for StatusEvent {
+        applied_by,
+        applied_to,
+        effect,
+    } in events.read().copied()
+    {
+        if !targets.contains(applied_to) {
+            continue;
+        }
+        // several effects can land on the same entity in one frame, so they're applied one at a time
+        commands.add(move |world: &mut World| {
+            let Some(mut entity) = world.get_entity_mut(applied_to) else {
+                return;
+            };
+            if let Some(mut effects) = entity.get_mut::<StatusEffects>() {
+                effects.add(applied_by, effect);
+            } else {
+                let mut effects = StatusEffects::default();
+                effects.add(applied_by, effect);
+                entity.insert(effects);
+            }
+        });
+    }
+}
+
+/// Wears off expired effects and deals burn damage
+fn tick_status_effects(
+    mut affected: Query<(Entity, &mut StatusEffects), Without<Dead>>,
+    mut damage: EventWriter<DamageEvent>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+    for (entity, mut effects) in affected.iter_mut() {
+        let effects = &mut *effects;
+        for stack in effects.stacks.iter_mut() {
+            stack.remaining -= dt;
+        }
+        effects.stacks.retain(|stack| stack.remaining > 0.0);
+        let burned_by = effects
+            .stacks_of(StatusKind::Burn)
+            .next()
+            .map(|stack| stack.applied_by);
+        let Some(burned_by) = burned_by else {
+            effects.burn_damage = 0.0;
+            continue;
+        };
+        let burn_rate = effects
+            .stacks_of(StatusKind::Burn)
+            .map(|stack| stack.effect.strength)
+            .sum::<f32>();
+        effects.burn_damage += burn_rate * dt;
+        let amount = effects.burn_damage.floor();
+        if amount >= 1.0 {
+            effects.burn_damage -= amount;
+            damage.send(DamageEvent {
+                damaged_by: burned_by,
+                applied_to: entity,
+                amount: amount as i32,
+                damage_type: DamageType::Fire,
+                critical: false,
+            });
+        }
+    }
+}
+
+fn tint_status_effects(mut affected: Query<(&StatusEffects, &mut Sprite), Without<Dead>>) {
+    for (effects, mut sprite) in affected.iter_mut() {
+        let tint = effects.tint();
+        if sprite.color != tint {
+            sprite.color = tint;
+        }
+    }
+}
+
+fn amplify_poisoned_damage(mut queue: ResMut<DamageQueue>, affected: Query<&StatusEffects>) {
+    for damage in queue.0.iter_mut() {
+        if let Ok(effects) = affected.get(damage.applied_to) {
+            damage.amount *= effects.damage_taken_multiplier();
+        }
+    }
+}
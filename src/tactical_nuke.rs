@@ -0,0 +1,138 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{
+    explosion::{Blast, BlastFilter, ExplosionEvent},
+    health::Dead,
+    loading::WeaponAssets,
+    modifiers::StatModifiers,
+    weapon::{BasicGun, Coord2D, Friendly, Gun},
+    weapon_definition::WeaponDefinition,
+    GameState, GameSystems,
+};
+
+pub struct TacticalNukePlugin;
+
+impl Plugin for TacticalNukePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            burn_fuses
+                .before(GameSystems::Collision)
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            FixedUpdate,
+            drop_nukes
+                .run_if(in_state(GameState::Playing))
+                .after(GameSystems::Collision),
+        )
+        .add_systems(OnEnter(GameState::Menu), cleanup_drop_zones);
+    }
+}
+
+/// How many times per second the drop zone flashes before the nuke lands
+const FLASHES_PER_SECOND: f32 = 3.0;
+
+pub struct TacticalNukeKind;
+
+impl BasicGun for TacticalNukeKind {
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition> {
+        &weapons.tactical_nuke
+    }
+}
+
+pub type TacticalNuke = Gun<TacticalNukeKind>;
+
+/// Marks where a nuke is about to land
+#[derive(Component)]
+struct DropZone {
+    dropped_by: Entity,
+    /// seconds until the nuke lands
+    fuse: f32,
+    blast: Blast,
+}
+
+fn drop_nukes(
+    mut commands: Commands,
+    mut bombers: Query<
+        (
+            Entity,
+            &mut TacticalNuke,
+            &Transform,
+            Option<&StatModifiers>,
+            Option<&Friendly>,
+        ),
+        Without<Dead>,
+    >,
+    weapons: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinition>>,
+    time: Res<Time>,
+) {
+    let Some(definition) = definitions.get(TacticalNukeKind::definition(&weapons)) else {
+        return;
+    };
+    for (dropped_by, mut nuke, transform, modifiers, friendly) in bombers.iter_mut() {
+        if nuke.cooldown_remaining > 0.0 {
+            nuke.cooldown_remaining -= time.delta_seconds();
+            continue;
+        }
+        let stats = definition.level(nuke.level).modified_by(modifiers);
+        nuke.cooldown_remaining = stats.cooldown;
+        let radius = stats.radius.unwrap_or(400.0);
+        commands.spawn((
+            SpriteBundle {
+                texture: definition.projectile_sprite.clone(),
+                transform: Transform::from_translation(
+                    Coord2D::from(transform.translation).extend(0.5),
+                ),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(radius * 2.0)),
+                    color: Color::rgba(1.0, 0.1, 0.1, 0.3),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            DropZone {
+                dropped_by,
+                fuse: stats.lifespan,
+                blast: Blast {
+                    radius,
+                    damage: stats.damage,
+                    falloff: 0.25,
+                    hurts: BlastFilter::opposing(friendly.is_some()),
+                },
+            },
+        ));
+    }
+}
+
+/// Flashes each drop zone and detonates it once the fuse runs out
+fn burn_fuses(
+    mut commands: Commands,
+    mut zones: Query<(Entity, &mut DropZone, &Transform, &mut Sprite)>,
+    mut explosions: EventWriter<ExplosionEvent>,
+    time: Res<Time>,
+) {
+    for (entity, mut zone, transform, mut sprite) in zones.iter_mut() {
+        zone.fuse -= time.delta_seconds();
+        if zone.fuse <= 0.0 {
+            explosions.send(ExplosionEvent {
+                caused_by: zone.dropped_by,
+                center: *Coord2D::from(transform.translation),
+                blast: zone.blast,
+            });
+            commands.entity(entity).despawn();
+        } else {
+            let flash = (zone.fuse * FLASHES_PER_SECOND * TAU).sin() * 0.5 + 0.5;
+            sprite.color.set_a(0.15 + flash * 0.3);
+        }
+    }
+}
+
+fn cleanup_drop_zones(mut commands: Commands, zones: Query<Entity, With<DropZone>>) {
+    for zone in zones.iter() {
+        commands.entity(zone).despawn();
+    }
+}
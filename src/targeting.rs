@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use bevy_rand::{prelude::WyRand, resource::GlobalEntropy};
+use enum_iterator::Sequence;
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+
+use crate::{
+    actions::Actions,
+    boomerang::BoomerangKind,
+    enemy::Enemy,
+    health::{Dead, DespawnTimer, Health},
+    heat_seeker::HeatSeekerKind,
+    leveling::XpWorth,
+    loading::WeaponAssets,
+    spatial_index::SpatialIndex,
+    weapon::{
+        BasicGun, BileKind, Coord2D, Friendly, Gun, LeafBlowerKind, MachineGunKind, PeaShooterKind,
+        SniperKind, TargetDistance,
+    },
+    weapon_definition::WeaponDefinition,
+    GameState, GameSystems,
+};
+
+pub struct TargetingPlugin;
+
+impl Plugin for TargetingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                aim_weapon::<MachineGunKind>,
+                aim_weapon::<PeaShooterKind>,
+                aim_weapon::<SniperKind>,
+                aim_weapon::<BileKind>,
+                aim_weapon::<LeafBlowerKind>,
+                aim_weapon::<BoomerangKind>,
+                aim_weapon::<HeatSeekerKind>,
+            )
+                .before(GameSystems::Collision)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// How far guns on ships without a [`TargetDistance`], like drones and wingmen, look for targets
+const DEFAULT_TARGET_DISTANCE: f32 = 600.0;
+
+/// How a weapon picks which enemy to shoot at
+#[derive(Deserialize, Sequence, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TargetingMode {
+    #[default]
+    Nearest,
+    LowestHealth,
+    HighestHealth,
+    /// the enemy that drops the most xp
+    MostXp,
+    /// any enemy in range, kept until it dies or gets away
+    Random,
+    /// shoots towards the cursor, or straight ahead when there isn't one
+    Manual,
+}
+
+impl std::fmt::Display for TargetingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TargetingMode::Nearest => "Nearest",
+            TargetingMode::LowestHealth => "Lowest Health",
+            TargetingMode::HighestHealth => "Highest Health",
+            TargetingMode::MostXp => "Most XP",
+            TargetingMode::Random => "Random",
+            TargetingMode::Manual => "Manual",
+        };
+        f.write_str(name)
+    }
+}
+
+/// What a friendly [`Gun`] picked with its [`TargetingMode`]
+#[derive(Clone, Copy, Default)]
+pub struct Aim {
+    pub target: Option<Entity>,
+    /// direction to the target, or where it was manually aimed
+    pub vector: Option<Vec2>,
+}
+
+/// An enemy that a weapon could pick
+struct Candidate {
+    entity: Entity,
+    distance: f32,
+    health: i32,
+    xp: i32,
+}
+
+/// Picks a target for every friendly `Gun<K>` on its own, so each weapon can prefer different enemies
+fn aim_weapon<K: BasicGun>(
+    mut shooters: Query<
+        (&mut Gun<K>, &Transform, Option<&TargetDistance>),
+        (With<Friendly>, Without<Dead>),
+    >,
+    enemies: Query<
+        (Entity, &Transform, Option<&Health>, Option<&XpWorth>),
+        (With<Enemy>, Without<DespawnTimer>),
+    >,
+    index: Res<SpatialIndex>,
+    actions: Res<Actions>,
+    weapons: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinition>>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+) {
+    let Some(definition) = definitions.get(K::definition(&weapons)) else {
+        return;
+    };
+    for (mut gun, transform, target_distance) in shooters.iter_mut() {
+        let position = *Coord2D::from(transform.translation);
+        let mode = gun.targeting.unwrap_or(definition.targeting);
+        if mode == TargetingMode::Manual {
+            let ahead = (transform.rotation * Vec3::Y).truncate();
+            let direction = actions
+                .aim_position
+                .and_then(|aim| (aim - position).try_normalize())
+                .unwrap_or(ahead);
+            gun.aim = Some(Aim {
+                target: None,
+                vector: Some(direction),
+            });
+            continue;
+        }
+        let max_distance = target_distance
+            .map(|t| t.0)
+            .unwrap_or(DEFAULT_TARGET_DISTANCE);
+        let candidate = |(entity, enemy, health, xp): (
+            Entity,
+            &Transform,
+            Option<&Health>,
+            Option<&XpWorth>,
+        )| {
+            let distance = (*Coord2D::from(enemy.translation) - position).length();
+            (distance < max_distance).then(|| Candidate {
+                entity,
+                distance,
+                health: health.map(|h| h.0).unwrap_or_default(),
+                xp: xp.map(|xp| xp.0).unwrap_or_default(),
+            })
+        };
+        let in_range = || -> Vec<Candidate> {
+            index
+                .candidates(position, max_distance)
+                .filter_map(|entity| enemies.get(entity).ok())
+                .filter_map(candidate)
+                .collect()
+        };
+        let closest_first = |a: &Candidate, b: &Candidate| b.distance.total_cmp(&a.distance);
+        let target = match mode {
+            TargetingMode::Nearest | TargetingMode::Manual => index
+                .nearest(position, max_distance, |entity| {
+                    let enemy = enemies.get(entity).ok()?;
+                    candidate(enemy).map(|candidate| candidate.distance)
+                })
+                .map(|(entity, _)| entity),
+            TargetingMode::LowestHealth => in_range()
+                .into_iter()
+                .max_by(|a, b| b.health.cmp(&a.health).then_with(|| closest_first(a, b)))
+                .map(|candidate| candidate.entity),
+            TargetingMode::HighestHealth => in_range()
+                .into_iter()
+                .max_by(|a, b| a.health.cmp(&b.health).then_with(|| closest_first(a, b)))
+                .map(|candidate| candidate.entity),
+            TargetingMode::MostXp => in_range()
+                .into_iter()
+                .max_by(|a, b| a.xp.cmp(&b.xp).then_with(|| closest_first(a, b)))
+                .map(|candidate| candidate.entity),
+            TargetingMode::Random => {
+                let current = gun.aim.and_then(|aim| aim.target);
+                let candidates = in_range();
+                candidates
+                    .iter()
+                    .find(|candidate| Some(candidate.entity) == current)
+                    .or_else(|| candidates.choose(&mut *rng))
+                    .map(|candidate| candidate.entity)
+            }
+        };
+        let vector = target
+            .and_then(|target| enemies.get(target).ok())
+            .map(|(_, enemy, _, _)| *Coord2D::from(enemy.translation) - position);
+        gun.aim = Some(Aim { target, vector });
+    }
+}
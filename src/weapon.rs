@@ -1,12 +1,21 @@
-use std::ops::Deref;
+use std::{marker::PhantomData, ops::Deref};
 
-use bevy::prelude::*;
+use bevy::{ecs::system::EntityCommands, prelude::*};
 
 use crate::{
+    collider::Collider,
+    critical_hits::CriticalHit,
     enemy::Enemy,
-    health::{DamageEvent, Dead, DespawnTimer, Health},
-    loading::TextureAssets,
+    explosion::{ExplodeOnImpact, ExplosionEvent},
+    health::{DamageEvent, DamageType, Dead, DespawnTimer, Health},
+    knockback::{Knockback, KnockbackEvent, Staggered},
+    loading::{TextureAssets, WeaponAssets},
+    modifiers::StatModifiers,
     player::{OrientTowardsVelocity, Player},
+    spatial_index::SpatialIndex,
+    status_effects::{InflictsStatus, StatusEffects, StatusEvent},
+    targeting::{Aim, TargetingMode},
+    weapon_definition::{Multishot, WeaponDefinition, WeaponLevel},
     GameState, GameSystems,
 };
 
@@ -38,10 +47,11 @@ impl Plugin for WeaponPlugin {
             (
                 update_target_vectors,
                 update_player_target,
-                shoot_basic_gun::<MachineGun>,
-                shoot_basic_gun::<PeaShooter>,
-                shoot_basic_gun::<Sniper>,
-                shoot_basic_gun::<Bile>,
+                shoot_basic_gun::<MachineGunKind>,
+                shoot_basic_gun::<PeaShooterKind>,
+                shoot_basic_gun::<SniperKind>,
+                shoot_basic_gun::<BileKind>,
+                shoot_basic_gun::<LeafBlowerKind>,
             )
                 .run_if(in_state(GameState::Playing))
                 .after(GameSystems::Collision),
@@ -56,15 +66,6 @@ fn cleanup_projectiles(mut commands: Commands, entities: Query<Entity, With<Proj
     }
 }
 
-/// [Weapon] should provide the state for whether the weapon should be firing or not
-#[derive(Component)]
-pub struct Weapon {
-    /// the base cooldown of the weapon
-    pub cooldown: f32,
-    /// the time left until the weapon fires again
-    pub cooldown_left: f32,
-}
-
 #[derive(Component, Default)]
 pub struct Velocity(pub Vec2);
 
@@ -77,12 +78,17 @@ pub struct VMax(pub f32);
 
 #[derive(Component)]
 pub struct Projectile {
-    fired_by: Entity,
-    damage_amount: i32,
-    /// size of the projectile
-    size: f32,
+    pub(crate) fired_by: Entity,
+    pub(crate) damage_amount: i32,
+    pub(crate) damage_type: DamageType,
+    /// set once the projectile rolls a [`CriticalHit`]
+    pub(crate) critical: bool,
 }
 
+/// How many more enemies a projectile can hit before it is destroyed
+#[derive(Component)]
+pub struct Pierce(pub u32);
+
 /// The entity that is being targeted by a weapon
 #[derive(Component)]
 pub struct Target(pub Option<Entity>);
@@ -91,41 +97,79 @@ pub struct Target(pub Option<Entity>);
 #[derive(Component)]
 pub struct TargetVector(pub Option<Vec2>);
 
+/// How far away a target can be before it is ignored
 #[derive(Component)]
 pub struct TargetDistance(pub f32);
 
-/// just tries to target the closest enemy
+/// Overrides how well a shooter leads moving targets, see [`crate::weapon_definition::WeaponLevel::accuracy`].
+/// Lets the same weapon be sloppy on one ship and deadly on another
+#[derive(Component)]
+pub struct Accuracy(pub f32);
+
+/// Where to aim so a projectile fired at `speed` meets a target at `offset` moving with `velocity`.
+/// `accuracy` scales how far ahead of the target to aim, from 0 (where it is now) to 1 (where it will be)
+fn lead_target(offset: Vec2, velocity: Vec2, speed: f32, accuracy: f32) -> Vec2 {
+    // solves |offset + velocity * t| = speed * t for the soonest time t the two can meet
+    let a = velocity.length_squared() - speed * speed;
+    let b = 2.0 * offset.dot(velocity);
+    let c = offset.length_squared();
+    let time = if a.abs() < f32::EPSILON {
+        (b < 0.0).then(|| -c / b)
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        (discriminant >= 0.0)
+            .then(|| {
+                let root = discriminant.sqrt();
+                [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+                    .into_iter()
+                    .filter(|t| *t > 0.0)
+                    .min_by(f32::total_cmp)
+            })
+            .flatten()
+    };
+    // targets that are too fast to ever catch are shot at where they are
+    offset + velocity * time.unwrap_or(0.0) * accuracy.clamp(0.0, 1.0)
+}
+
+/// just tries to target the closest enemy within [`TargetDistance`]
 fn update_player_target(
-    mut player: Query<(&mut Target, &Transform), With<Player>>,
-    enemies: Query<(Entity, &Transform), (With<Enemy>, Without<DespawnTimer>)>,
+    mut player: Query<(&mut Target, &Transform, Option<&TargetDistance>), With<Player>>,
+    enemies: Query<&Transform, (With<Enemy>, Without<DespawnTimer>)>,
+    index: Res<SpatialIndex>,
 ) {
-    if let Ok((mut target, start)) = player.get_single_mut() {
-        target.0 = enemies
-            .iter()
-            .map(|(entity, enemy)| {
-                (
-                    entity,
-                    (*Coord2D::from(start.translation) - *Coord2D::from(enemy.translation))
-                        .length(),
-                )
+    if let Ok((mut target, start, target_distance)) = player.get_single_mut() {
+        let start = *Coord2D::from(start.translation);
+        let max_distance = target_distance.map(|t| t.0).unwrap_or(f32::INFINITY);
+        target.0 = index
+            .nearest(start, max_distance, |entity| {
+                let enemy = enemies.get(entity).ok()?;
+                Some((start - *Coord2D::from(enemy.translation)).length())
             })
-            .min_by_key(|(_, dist)| *dist as i32)
             .map(|(e, _)| e);
     }
 }
 
 fn update_acceleration(
-    mut velocities: Query<(&mut Velocity, &ConstantAcceleration, Option<&VMax>)>,
+    mut velocities: Query<
+        (
+            &mut Velocity,
+            &ConstantAcceleration,
+            Option<&VMax>,
+            Option<&StatusEffects>,
+        ),
+        Without<Staggered>,
+    >,
     time: Res<Time>,
 ) {
     let dt = time.delta_seconds();
     velocities
         .par_iter_mut()
-        .for_each(|(mut velocity, accel, vmax)| {
+        .for_each(|(mut velocity, accel, vmax, status)| {
             let vel = velocity.0;
             velocity.0 += vel * (dt * accel.0);
             if let Some(vmax) = vmax {
-                velocity.0 = velocity.0.clamp_length_max(vmax.0);
+                let slowed = status.map(|s| s.speed_multiplier()).unwrap_or(1.0);
+                velocity.0 = velocity.0.clamp_length_max(vmax.0 * slowed);
             }
         });
 }
@@ -169,11 +213,103 @@ impl From<Vec3> for Coord2D {
 }
 
 #[derive(Component)]
-struct DeathParticles(Option<Box<dyn Fn(&mut Commands, Transform) + Send + Sync>>);
+pub(crate) struct DeathParticles(
+    pub(crate) Option<Box<dyn Fn(&mut Commands, Transform) + Send + Sync>>,
+);
+
+impl DeathParticles {
+    /// Leaves a short lived impact sprite wherever the projectile hits something
+    pub(crate) fn impact(texture: Handle<Image>) -> Self {
+        DeathParticles(Some(Box::new(move |cmds, transform| {
+            cmds.spawn((
+                SpriteBundle {
+                    texture: texture.clone(),
+                    transform,
+                    ..Default::default()
+                },
+                DespawnTimer(0.2),
+            ));
+        })))
+    }
+}
+
+/// The components every projectile fired from a weapon starts with
+#[derive(Bundle)]
+pub(crate) struct ProjectileBundle {
+    sprite: SpriteBundle,
+    projectile: Projectile,
+    velocity: Velocity,
+    /// projectiles can be shot down by the other team
+    health: Health,
+    pierce: Pierce,
+    death_particles: DeathParticles,
+    despawn_timer: DespawnTimer,
+}
 
-/// The entity that was last hit
+impl ProjectileBundle {
+    pub(crate) fn new(
+        fired_by: Entity,
+        definition: &WeaponDefinition,
+        stats: &WeaponLevel,
+        transform: &Transform,
+        direction: Vec2,
+        impact: Handle<Image>,
+    ) -> Self {
+        ProjectileBundle {
+            sprite: SpriteBundle {
+                texture: definition.projectile_sprite.clone(),
+                transform: transform.with_scale(Vec3::splat(1.0)),
+                sprite: Sprite {
+                    custom_size: definition.projectile_size,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            projectile: Projectile {
+                fired_by,
+                damage_amount: stats.damage,
+                damage_type: definition.damage_type,
+                critical: false,
+            },
+            velocity: Velocity(direction * stats.projectile_velocity),
+            health: Health(stats.pierce as i32),
+            pierce: Pierce(stats.pierce),
+            death_particles: DeathParticles::impact(impact),
+            despawn_timer: DespawnTimer(stats.lifespan),
+        }
+    }
+}
+
+/// Spawns a projectile on the same team as whoever fired it, with the status effect, critical hit and knockback stats of its weapon
+pub(crate) fn spawn_projectile<'a>(
+    commands: &'a mut Commands,
+    bundle: ProjectileBundle,
+    stats: &WeaponLevel,
+    friendly: bool,
+    hostile: bool,
+) -> EntityCommands<'a> {
+    let mut entity = commands.spawn(bundle);
+    if let Some(status) = stats.status {
+        entity.insert(InflictsStatus(status));
+    }
+    if let Some(crit) = CriticalHit::from_stats(stats) {
+        entity.insert(crit);
+    }
+    if stats.knockback > 0.0 {
+        entity.insert(Knockback(stats.knockback));
+    }
+    if friendly {
+        entity.insert(Friendly);
+    }
+    if hostile {
+        entity.insert(Hostile);
+    }
+    entity
+}
+
+/// Every entity a projectile has already hit, so piercing projectiles never hit the same entity twice
 #[derive(Component)]
-struct LastHit(Entity);
+struct AlreadyHit(Vec<Entity>);
 
 /// Checks collisions between particles
 fn check_bullet_collisions_teamed<A, B>(
@@ -182,14 +318,20 @@ fn check_bullet_collisions_teamed<A, B>(
         (
             Entity,
             &Transform,
+            &Collider,
             &Projectile,
             Option<&mut DeathParticles>,
-            &mut Health,
-            Option<&mut LastHit>,
+            &mut Pierce,
+            Option<&mut AlreadyHit>,
+            Option<&ExplodeOnImpact>,
+            Option<&InflictsStatus>,
+            &Velocity,
+            Option<&Knockback>,
         ),
-        With<A>,
+        (With<A>, Without<Dead>),
     >,
-    other_entities: Query<(Entity, &Transform), (With<Health>, With<B>)>,
+    other_entities: Query<(&Transform, &Collider), (With<Health>, With<B>)>,
+    index: Res<SpatialIndex>,
 ) where
     A: Component,
     B: Component,
@@ -198,51 +340,93 @@ fn check_bullet_collisions_teamed<A, B>(
         |(
             self_bullet,
             transform,
+            collider,
             Projectile {
                 fired_by,
                 damage_amount,
-                size,
+                damage_type,
+                critical,
             },
             death_particles,
-            mut health,
-            last_hit,
+            mut pierce,
+            already_hit,
+            explosive,
+            status,
+            velocity,
+            knockback,
         )| {
-            for (entity, other_transform) in &other_entities {
+            let position = *Coord2D::from(transform.translation);
+            for entity in index.candidates(position, collider.bounding_radius(transform)) {
+                let Ok((other_transform, other_collider)) = other_entities.get(entity) else {
+                    continue;
+                };
                 // don't collide with sender
                 let fired_by = *fired_by;
                 if self_bullet != entity
                     && fired_by != entity
-                    && last_hit.as_ref().map(|l| l.0 != entity).unwrap_or(true)
+                    && !already_hit
+                        .as_ref()
+                        .is_some_and(|hit| hit.0.contains(&entity))
+                    && collider.overlaps(transform, other_collider, other_transform)
                 {
-                    let delta = *Coord2D::from(transform.translation)
-                        - *Coord2D::from(other_transform.translation);
-                    if delta.length() < *size {
-                        let amount = *damage_amount;
-                        health.0 -= 1;
-
-                        commands.command_scope(|mut cmds| {
-                            if health.0 <= 0 {
-                                cmds.entity(self_bullet).despawn();
-                            } else if let Some(mut hit) = last_hit {
-                                hit.0 = entity;
-                            } else {
-                                cmds.entity(self_bullet).try_insert(LastHit(entity));
-                            }
-                            if let Some(death_particles) =
-                                death_particles.as_ref().and_then(|u| u.0.as_ref())
-                            {
-                                death_particles(&mut cmds, *transform);
-                            }
+                    let amount = *damage_amount;
+                    let damage_type = *damage_type;
+                    let critical = *critical;
+                    pierce.0 = pierce.0.saturating_sub(1);
+
+                    commands.command_scope(|mut cmds| {
+                        if pierce.0 == 0 {
+                            cmds.entity(self_bullet).despawn();
+                        } else if let Some(mut hit) = already_hit {
+                            hit.0.push(entity);
+                        } else {
+                            cmds.entity(self_bullet)
+                                .try_insert(AlreadyHit(vec![entity]));
+                        }
+                        if let Some(death_particles) =
+                            death_particles.as_ref().and_then(|u| u.0.as_ref())
+                        {
+                            death_particles(&mut cmds, *transform);
+                        }
+                        if let Some(ExplodeOnImpact(blast)) = explosive.copied() {
+                            let center = *Coord2D::from(transform.translation);
                             cmds.add(move |w: &mut World| {
-                                w.send_event(DamageEvent {
-                                    damaged_by: fired_by,
+                                w.send_event(ExplosionEvent {
+                                    caused_by: fired_by,
+                                    center,
+                                    blast,
+                                });
+                            });
+                        }
+                        if let Some(Knockback(strength)) = knockback.copied() {
+                            let impulse = velocity.0.normalize_or_zero() * strength;
+                            cmds.add(move |w: &mut World| {
+                                w.send_event(KnockbackEvent {
+                                    applied_to: entity,
+                                    impulse,
+                                });
+                            });
+                        }
+                        if let Some(InflictsStatus(effect)) = status.copied() {
+                            cmds.add(move |w: &mut World| {
+                                w.send_event(StatusEvent {
+                                    applied_by: fired_by,
                                     applied_to: entity,
-                                    amount,
+                                    effect,
                                 });
                             });
+                        }
+                        cmds.add(move |w: &mut World| {
+                            w.send_event(DamageEvent {
+                                damaged_by: fired_by,
+                                applied_to: entity,
+                                amount,
+                                damage_type,
+                                critical,
+                            });
                         });
-                        break;
-                    }
+                    });
+                    break;
                 }
             }
         },
@@ -252,7 +436,7 @@ fn check_bullet_collisions_teamed<A, B>(
 fn update_target_vectors(
     mut weapons: Query<
         (Entity, &mut TargetVector, &Target, Option<&TargetDistance>),
-        Without<DespawnTimer>,
+        Without<Dead>,
     >,
     transforms: Query<&Transform>,
 ) {
@@ -272,308 +456,272 @@ fn update_target_vectors(
         });
 }
 
-trait BasicGun {
-    /// how long of a cooldown before this weapon can fire again
-    fn cooldown(&self) -> f32;
-    /// how much damage the bullet should do
-    fn damage(&self) -> i32;
-    /// how fast the projectile should shoot
-    fn projectile_velocity(&self) -> f32;
-    /// grab an image handle for the bullet
-    fn projectile_sprite(assets: &Res<TextureAssets>) -> Handle<Image>;
-    /// how much longer this gun has before it can fire again
-    fn cooldown_remaining(&mut self) -> &mut f32;
-    /// how much health the bullet should have
-    fn health(&self) -> i32;
-    /// how long in seconds the bullet should live for
-    fn bullet_lifespan(&self) -> f32;
-}
-
-#[derive(Component)]
-pub struct MachineGun {
-    level: u8,
-    cooldown_remaining: f32,
-}
+/// Picks the [`WeaponDefinition`] that a [`Gun`] reads its level curve from
+pub trait BasicGun: Send + Sync + 'static {
+    /// seconds after being equipped before the gun first fires
+    const INITIAL_COOLDOWN: f32 = 0.5;
 
-impl MachineGun {
-    pub fn new(level: u8) -> Self {
-        MachineGun {
-            level,
-            cooldown_remaining: 0.5,
-        }
-    }
-}
-
-impl BasicGun for MachineGun {
-    fn cooldown(&self) -> f32 {
-        match self.level {
-            0 => 0.5,
-            1 => 0.5,
-            2 => 0.4,
-            3 => 0.3,
-            4 => 0.2,
-            5 => 0.1,
-            _ => 0.05,
-        }
-    }
-
-    fn damage(&self) -> i32 {
-        match self.level {
-            0..=2 => 2,
-            3..=5 => 5,
-            _ => 6,
-        }
-    }
-
-    fn projectile_velocity(&self) -> f32 {
-        150.0
-    }
-
-    fn projectile_sprite(assets: &Res<TextureAssets>) -> Handle<Image> {
-        assets.bullet.clone()
-    }
-
-    fn cooldown_remaining(&mut self) -> &mut f32 {
-        &mut self.cooldown_remaining
-    }
-
-    fn health(&self) -> i32 {
-        1
-    }
-
-    fn bullet_lifespan(&self) -> f32 {
-        10.0
-    }
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition>;
 }
 
+/// A gun whose stats come from the [`WeaponDefinition`] selected by `K`
 #[derive(Component)]
-pub struct PeaShooter {
-    level: u8,
-    cooldown_remaining: f32,
+pub struct Gun<K: BasicGun> {
+    pub(crate) level: u8,
+    pub(crate) cooldown_remaining: f32,
+    /// shots left to fire in the current [`Multishot::Burst`]
+    burst_remaining: u32,
+    /// time until the next shot of the current burst
+    burst_delay: f32,
+    /// overrides the [`TargetingMode`] from the weapon's definition
+    pub(crate) targeting: Option<TargetingMode>,
+    /// set when the gun picks its own target, otherwise it shoots at its owner's [`Target`]
+    pub(crate) aim: Option<Aim>,
+    kind: PhantomData<K>,
 }
 
-impl PeaShooter {
+impl<K: BasicGun> Gun<K> {
     pub fn new(level: u8) -> Self {
-        PeaShooter {
+        Gun {
             level,
-            cooldown_remaining: 0.5,
-        }
-    }
-}
-
-impl BasicGun for PeaShooter {
-    fn cooldown(&self) -> f32 {
-        match self.level {
-            0 => 2.5,
-            1 => 1.0,
-            2 => 0.9,
-            3 => 0.8,
-            4 => 0.7,
-            5 => 0.6,
-            _ => 0.5,
-        }
-    }
-
-    fn damage(&self) -> i32 {
-        match self.level {
-            0..=2 => 5,
-            3..=5 => 10,
-            _ => 6,
+            cooldown_remaining: K::INITIAL_COOLDOWN,
+            burst_remaining: 0,
+            burst_delay: 0.0,
+            targeting: None,
+            aim: None,
+            kind: PhantomData,
         }
     }
 
-    fn projectile_velocity(&self) -> f32 {
-        200.0
-    }
-
-    fn projectile_sprite(assets: &Res<TextureAssets>) -> Handle<Image> {
-        assets.pea.clone()
-    }
-
-    fn cooldown_remaining(&mut self) -> &mut f32 {
-        &mut self.cooldown_remaining
+    /// Equips this gun at `level`. A gun that is already equipped keeps its cooldown
+    /// so long cooldown weapons aren't reset every time a powerup is picked
+    pub fn equip(entity: &mut EntityCommands, level: u8, targeting: Option<TargetingMode>) {
+        entity.add(move |mut entity: EntityWorldMut| {
+            if let Some(mut gun) = entity.get_mut::<Gun<K>>() {
+                gun.level = level;
+                gun.targeting = targeting;
+            } else {
+                entity.insert(Gun::<K> {
+                    targeting,
+                    ..Gun::new(level)
+                });
+            }
+        });
     }
 
-    fn health(&self) -> i32 {
-        2
+    /// The entity this gun is shooting at
+    pub fn target(&self, owner_target: &Target) -> Option<Entity> {
+        self.aim.map_or(owner_target.0, |aim| aim.target)
     }
 
-    fn bullet_lifespan(&self) -> f32 {
-        10.0
+    /// The direction this gun is shooting in
+    pub fn target_vector(&self, owner_vector: &TargetVector) -> Option<Vec2> {
+        self.aim.map_or(owner_vector.0, |aim| aim.vector)
     }
 }
 
-#[derive(Component)]
-pub struct Sniper {
-    level: u8,
-    cooldown_remaining: f32,
-}
+pub struct MachineGunKind;
 
-impl Sniper {
-    pub fn new(level: u8) -> Self {
-        Sniper {
-            level,
-            cooldown_remaining: 0.5,
-        }
+impl BasicGun for MachineGunKind {
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition> {
+        &weapons.machine_gun
     }
 }
 
-impl BasicGun for Sniper {
-    fn cooldown(&self) -> f32 {
-        (1.0 / (self.level.min(2) as f32).log10()).max(3.0)
-    }
-
-    fn damage(&self) -> i32 {
-        (self.level as i32).min(1) * 20
-    }
-
-    fn projectile_velocity(&self) -> f32 {
-        500.0
-    }
-
-    fn health(&self) -> i32 {
-        (self.level as i32).min(2) * 2
-    }
-
-    fn projectile_sprite(assets: &Res<TextureAssets>) -> Handle<Image> {
-        assets.bullet.clone()
-    }
+pub type MachineGun = Gun<MachineGunKind>;
 
-    fn cooldown_remaining(&mut self) -> &mut f32 {
-        &mut self.cooldown_remaining
-    }
+pub struct PeaShooterKind;
 
-    fn bullet_lifespan(&self) -> f32 {
-        (self.level as f32).min(10.0)
+impl BasicGun for PeaShooterKind {
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition> {
+        &weapons.pea_shooter
     }
 }
 
-#[derive(Component)]
-pub(crate) struct Bile {
-    level: u8,
-    cooldown_remaining: f32,
-}
+pub type PeaShooter = Gun<PeaShooterKind>;
 
-impl Bile {
-    pub(crate) fn new(level: u8) -> Self {
-        Self {
-            level,
-            cooldown_remaining: 0.1,
-        }
+pub struct SniperKind;
+
+impl BasicGun for SniperKind {
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition> {
+        &weapons.sniper
     }
 }
 
-impl BasicGun for Bile {
-    fn cooldown(&self) -> f32 {
-        const COOLDOWNS: &[f32] = &[0.2, 0.15, 0.15, 0.12, 0.1, 0.1, 0.01];
-        COOLDOWNS.get(self.level as usize).copied().unwrap_or(0.01)
-    }
+pub type Sniper = Gun<SniperKind>;
 
-    fn damage(&self) -> i32 {
-        (self.level as i32).min(1)
-    }
+pub struct BileKind;
 
-    fn projectile_velocity(&self) -> f32 {
-        250.0
-    }
+impl BasicGun for BileKind {
+    const INITIAL_COOLDOWN: f32 = 0.1;
 
-    fn projectile_sprite(assets: &Res<TextureAssets>) -> Handle<Image> {
-        assets.bile.clone()
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition> {
+        &weapons.bile
     }
+}
 
-    fn cooldown_remaining(&mut self) -> &mut f32 {
-        &mut self.cooldown_remaining
-    }
+pub type Bile = Gun<BileKind>;
 
-    fn health(&self) -> i32 {
-        (self.level as i32).min(1)
-    }
+pub struct LeafBlowerKind;
 
-    fn bullet_lifespan(&self) -> f32 {
-        0.2
+impl BasicGun for LeafBlowerKind {
+    fn definition(weapons: &WeaponAssets) -> &Handle<WeaponDefinition> {
+        &weapons.leaf_blower
     }
 }
 
-fn shoot_basic_gun<T>(
+pub type LeafBlower = Gun<LeafBlowerKind>;
+
+fn shoot_basic_gun<K>(
     commands: ParallelCommands,
     mut gun_query: Query<
         (
             Entity,
             &TargetVector,
-            &mut T,
+            &mut Gun<K>,
             &Transform,
-            Option<&SpecialMunitions>,
+            &Target,
+            Option<&Accuracy>,
+            Option<&StatModifiers>,
             Option<&Friendly>,
             Option<&Enemy>,
         ),
         Without<Dead>,
     >,
+    velocities: Query<&Velocity>,
     textures: Res<TextureAssets>,
+    weapons: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinition>>,
     time: Res<Time>,
 ) where
-    T: Component + BasicGun + Sized,
+    K: BasicGun,
 {
     let dt = time.delta_seconds();
     gun_query.par_iter_mut().for_each(
-        |(fired_by, vector, mut gun, transform, munitions, friendly, enemy)| {
-            if let Some(target_vector) = vector.0 {
-                let target_distance = target_vector.length();
-                let estimated_distance = gun.bullet_lifespan() * gun.projectile_velocity() * 2.0;
-                if *gun.cooldown_remaining() <= 0.0 && target_distance < estimated_distance {
-                    *gun.cooldown_remaining() = gun.cooldown();
-                    let death_texture = textures.bullet_impact.clone();
-                    commands.command_scope(|mut cmd| {
-                        let mut entity = cmd.spawn((
-                            SpriteBundle {
-                                texture: T::projectile_sprite(&textures),
-                                transform: transform.with_scale(Vec3::splat(1.0)),
-                                ..Default::default()
-                            },
-                            Projectile {
-                                fired_by,
-                                damage_amount: gun.damage()
-                                    * munitions.map(|m| m.damage_mult()).unwrap_or(1),
-                                size: 40.0,
-                            },
-                            Velocity(target_vector.normalize() * gun.projectile_velocity()),
-                            Health(gun.health()),
-                            DeathParticles(Some(Box::new(move |cmds, transform| {
-                                cmds.spawn((
-                                    SpriteBundle {
-                                        texture: death_texture.clone(),
-                                        transform,
-                                        ..Default::default()
-                                    },
-                                    DespawnTimer(0.2),
-                                ));
-                            }))),
-                            DespawnTimer(gun.bullet_lifespan()),
-                            OrientTowardsVelocity,
-                        ));
-                        if friendly.is_some() {
-                            entity.insert(Friendly);
-                        }
-                        if enemy.is_some() {
-                            entity.insert(Hostile);
-                        }
-                    });
-                } else {
-                    *gun.cooldown_remaining() -= dt;
+        |(fired_by, vector, mut gun, transform, target, accuracy, modifiers, friendly, enemy)| {
+            let Some(definition) = definitions.get(K::definition(&weapons)) else {
+                return;
+            };
+            let stats = definition.level(gun.level).modified_by(modifiers);
+            let Some(target_vector) = gun.target_vector(vector) else {
+                // a burst is abandoned as soon as the target is lost
+                gun.burst_remaining = 0;
+                return;
+            };
+            let fire = |directions: &mut dyn Iterator<Item = Vec2>| {
+                commands.command_scope(|mut cmd| {
+                    for direction in directions {
+                        let bundle = ProjectileBundle::new(
+                            fired_by,
+                            definition,
+                            &stats,
+                            transform,
+                            direction,
+                            textures.bullet_impact.clone(),
+                        );
+                        let mut entity = spawn_projectile(
+                            &mut cmd,
+                            bundle,
+                            &stats,
+                            friendly.is_some(),
+                            enemy.is_some(),
+                        );
+                        entity.insert(OrientTowardsVelocity);
+                    }
+                });
+            };
+            let target_velocity = gun
+                .target(target)
+                .and_then(|target| velocities.get(target).ok())
+                .map(|velocity| velocity.0)
+                .unwrap_or_default();
+            let accuracy = accuracy.map(|a| a.0).unwrap_or(stats.accuracy);
+            let direction = lead_target(
+                target_vector,
+                target_velocity,
+                stats.projectile_velocity,
+                accuracy,
+            )
+            .normalize();
+            if gun.burst_remaining > 0 {
+                gun.burst_delay -= dt;
+                if gun.burst_delay <= 0.0 {
+                    if let Some(delay) = definition.multishot.burst_delay(&stats) {
+                        gun.burst_delay = delay;
+                    }
+                    gun.burst_remaining -= 1;
+                    fire(&mut std::iter::once(direction));
+                }
+            }
+            let target_distance = target_vector.length();
+            let estimated_distance = stats.lifespan * stats.projectile_velocity * 2.0;
+            // a new volley waits for the last burst to finish so extra projectiles are never lost
+            if gun.cooldown_remaining <= 0.0
+                && gun.burst_remaining == 0
+                && target_distance < estimated_distance
+            {
+                gun.cooldown_remaining = stats.cooldown;
+                match definition.multishot {
+                    Multishot::Spread(spread) => {
+                        let middle = (stats.count - 1) as f32 / 2.0;
+                        fire(&mut (0..stats.count).map(|index| {
+                            Vec2::from_angle((index as f32 - middle) * spread).rotate(direction)
+                        }));
+                    }
+                    Multishot::Burst(_) => {
+                        gun.burst_remaining = stats.count - 1;
+                        gun.burst_delay = definition.multishot.burst_delay(&stats).unwrap_or(0.0);
+                        fire(&mut std::iter::once(direction));
+                    }
                 }
+            } else {
+                gun.cooldown_remaining -= dt;
             }
         },
     );
 }
 
-#[derive(Component)]
-pub struct SpecialMunitions(u8);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl SpecialMunitions {
-    pub fn new(level: u8) -> Self {
-        Self(level)
+    #[test]
+    fn lead_target_aims_at_a_still_target() {
+        let offset = Vec2::new(100.0, 0.0);
+        assert_eq!(lead_target(offset, Vec2::ZERO, 50.0, 1.0), offset);
+    }
+
+    #[test]
+    fn lead_target_meets_a_moving_target() {
+        // meets the target 2.5 seconds later, 125 away
+        let aim = lead_target(Vec2::new(100.0, 0.0), Vec2::new(0.0, 30.0), 50.0, 1.0);
+        assert!(aim.abs_diff_eq(Vec2::new(100.0, 75.0), 1e-3), "{aim}");
+    }
+
+    #[test]
+    fn lead_target_meets_a_target_as_fast_as_the_projectile() {
+        let aim = lead_target(Vec2::new(100.0, 0.0), Vec2::new(-50.0, 0.0), 50.0, 1.0);
+        assert!(aim.abs_diff_eq(Vec2::new(50.0, 0.0), 1e-3), "{aim}");
+    }
+
+    #[test]
+    fn lead_target_aims_at_a_target_that_can_not_be_caught() {
+        let offset = Vec2::new(100.0, 0.0);
+        assert_eq!(
+            lead_target(offset, Vec2::new(100.0, 0.0), 50.0, 1.0),
+            offset
+        );
     }
 
-    fn damage_mult(&self) -> i32 {
-        self.0 as i32
+    #[test]
+    fn lead_target_scales_the_lead_by_accuracy() {
+        let offset = Vec2::new(100.0, 0.0);
+        let velocity = Vec2::new(0.0, 30.0);
+        assert_eq!(lead_target(offset, velocity, 50.0, 0.0), offset);
+        let aim = lead_target(offset, velocity, 50.0, 0.5);
+        assert!(aim.abs_diff_eq(Vec2::new(100.0, 37.5), 1e-3), "{aim}");
+        // accuracy above 1 never overshoots
+        let aim = lead_target(offset, velocity, 50.0, 2.0);
+        assert!(aim.abs_diff_eq(Vec2::new(100.0, 75.0), 1e-3), "{aim}");
     }
 }
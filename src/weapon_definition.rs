@@ -0,0 +1,418 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use ron::extensions::Extensions;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    health::DamageType,
+    modifiers::{Stat, StatModifiers},
+    status_effects::StatusEffect,
+    targeting::TargetingMode,
+};
+
+pub struct WeaponDefinitionPlugin;
+
+impl Plugin for WeaponDefinitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<WeaponDefinition>()
+            .register_asset_loader(WeaponDefinitionLoader);
+    }
+}
+
+/// Describes a weapon and how its stats scale with each level.
+/// Loaded from `*.weapon.ron` files so weapons can be tuned without recompiling.
+#[derive(Asset, TypePath, Debug)]
+pub struct WeaponDefinition {
+    /// the image used for every projectile this weapon fires
+    #[dependency]
+    pub projectile_sprite: Handle<Image>,
+    /// overrides the size the projectile sprite is drawn at
+    pub projectile_size: Option<Vec2>,
+    /// how the projectiles are fired when the weapon shoots more than one at a time
+    pub multishot: Multishot,
+    pub damage_type: DamageType,
+    /// how the weapon picks its target unless the player chooses otherwise
+    pub targeting: TargetingMode,
+    /// stats for each level, indexed by level. Levels past the end use the last entry
+    pub levels: Vec<WeaponLevel>,
+}
+
+impl WeaponDefinition {
+    /// Gets the stats for the given level, clamping to the highest level defined
+    pub fn level(&self, level: u8) -> &WeaponLevel {
+        let index = (level as usize).min(self.levels.len() - 1);
+        &self.levels[index]
+    }
+}
+
+/// How a weapon fires when its [`WeaponLevel::count`] is more than one
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub enum Multishot {
+    /// every projectile is fired at once, fanned out by this many radians
+    Spread(f32),
+    /// projectiles are fired one after another, this many seconds apart
+    Burst(f32),
+}
+
+impl Multishot {
+    /// Radians between each projectile of a volley. Bursts all fire in the same direction
+    pub fn spread(&self) -> f32 {
+        match self {
+            Multishot::Spread(spread) => *spread,
+            Multishot::Burst(_) => 0.0,
+        }
+    }
+
+    /// Seconds between each projectile of a burst, squeezed so the whole burst fits in the cooldown
+    pub fn burst_delay(&self, stats: &WeaponLevel) -> Option<f32> {
+        match self {
+            Multishot::Spread(_) => None,
+            Multishot::Burst(delay) => Some(delay.min(stats.cooldown / stats.count as f32)),
+        }
+    }
+}
+
+impl Default for Multishot {
+    fn default() -> Self {
+        Multishot::Spread(0.2)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WeaponLevel {
+    /// how long of a cooldown before this weapon can fire again
+    pub cooldown: f32,
+    /// how much damage the bullet should do
+    pub damage: i32,
+    /// how fast the projectile should shoot
+    pub projectile_velocity: f32,
+    /// how many enemies the projectile can hit before it is destroyed
+    pub pierce: u32,
+    /// how long in seconds the bullet should live for
+    pub lifespan: f32,
+    /// how many projectiles are fired each time the weapon shoots
+    #[serde(default = "default_count")]
+    pub count: u32,
+    /// how far the projectile travels before it turns back, for weapons that return to the shooter
+    #[serde(default)]
+    pub range: Option<f32>,
+    /// radius of the blast, for weapons that explode on impact
+    #[serde(default)]
+    pub radius: Option<f32>,
+    /// inflicted on anything the projectile hits
+    #[serde(default)]
+    pub status: Option<StatusEffect>,
+    /// chance from 0.0 to 1.0 of a projectile being a critical hit
+    #[serde(default)]
+    pub crit_chance: f32,
+    /// how much the damage of a critical hit is multiplied by
+    #[serde(default = "default_crit_multiplier")]
+    pub crit_multiplier: f32,
+    /// how hard the projectile pushes whatever it hits
+    #[serde(default)]
+    pub knockback: f32,
+    /// how far ahead of moving targets to aim, from 0.0 (where the target is) to 1.0 (where it will be)
+    #[serde(default)]
+    pub accuracy: f32,
+}
+
+impl WeaponLevel {
+    /// Applies the stat modifiers of whoever is using the weapon
+    pub fn modified_by(&self, modifiers: Option<&StatModifiers>) -> WeaponLevel {
+        let Some(modifiers) = modifiers else {
+            return self.clone();
+        };
+        WeaponLevel {
+            cooldown: modifiers.apply(Stat::Cooldown, self.cooldown),
+            damage: modifiers.apply(Stat::Damage, self.damage as f32).round() as i32,
+            projectile_velocity: modifiers.apply(Stat::ProjectileSpeed, self.projectile_velocity),
+            pierce: modifiers
+                .apply(Stat::Pierce, self.pierce as f32)
+                .round()
+                .max(1.0) as u32,
+            count: modifiers
+                .apply(Stat::ProjectileCount, self.count as f32)
+                .round()
+                .max(1.0) as u32,
+            radius: self
+                .radius
+                .map(|radius| modifiers.apply(Stat::Area, radius)),
+            crit_chance: modifiers
+                .apply(Stat::CritChance, self.crit_chance)
+                .clamp(0.0, 1.0),
+            crit_multiplier: modifiers.apply(Stat::CritMultiplier, self.crit_multiplier),
+            ..self.clone()
+        }
+    }
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+fn default_crit_multiplier() -> f32 {
+    2.0
+}
+
+/// The on disk representation of a [`WeaponDefinition`]
+#[derive(Deserialize)]
+struct WeaponDefinitionFile {
+    /// used to identify the weapon when reporting errors
+    name: String,
+    projectile_sprite: String,
+    #[serde(default)]
+    projectile_size: Option<f32>,
+    #[serde(default)]
+    multishot: Multishot,
+    #[serde(default)]
+    damage_type: DamageType,
+    #[serde(default)]
+    targeting: TargetingMode,
+    levels: Vec<WeaponLevel>,
+}
+
+#[derive(Debug, Error)]
+pub enum WeaponDefinitionError {
+    #[error("could not read weapon definition: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse weapon definition: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("weapon `{0}` projectile_size must be a positive number")]
+    InvalidSize(String),
+    #[error("weapon `{0}` multishot spread and burst delay can not be negative")]
+    InvalidMultishot(String),
+    #[error("weapon `{0}` must define at least one level")]
+    NoLevels(String),
+    #[error("weapon `{name}` level {level}: {reason}")]
+    InvalidLevel {
+        name: String,
+        level: usize,
+        reason: &'static str,
+    },
+}
+
+impl WeaponDefinitionFile {
+    fn parse(bytes: &[u8]) -> Result<Self, WeaponDefinitionError> {
+        // lets optional stats be written without wrapping them in `Some(..)`
+        let file: WeaponDefinitionFile = ron::Options::default()
+            .with_default_extension(Extensions::IMPLICIT_SOME)
+            .from_bytes(bytes)?;
+        file.validate()?;
+        Ok(file)
+    }
+
+    fn validate(&self) -> Result<(), WeaponDefinitionError> {
+        if self
+            .projectile_size
+            .is_some_and(|size| !size.is_finite() || size <= 0.0)
+        {
+            return Err(WeaponDefinitionError::InvalidSize(self.name.clone()));
+        }
+        let (Multishot::Spread(value) | Multishot::Burst(value)) = self.multishot;
+        if !value.is_finite() || value < 0.0 {
+            return Err(WeaponDefinitionError::InvalidMultishot(self.name.clone()));
+        }
+        if self.levels.is_empty() {
+            return Err(WeaponDefinitionError::NoLevels(self.name.clone()));
+        }
+        for (level, stats) in self.levels.iter().enumerate() {
+            // an infinite cooldown is allowed, the weapon just never fires a second time
+            let reason = if stats.cooldown.is_nan() || stats.cooldown <= 0.0 {
+                "cooldown must be a positive number"
+            } else if !stats.projectile_velocity.is_finite() || stats.projectile_velocity < 0.0 {
+                "projectile_velocity can not be negative"
+            } else if !stats.lifespan.is_finite() || stats.lifespan < 0.0 {
+                "lifespan can not be negative"
+            } else if stats.damage < 0 {
+                "damage can not be negative"
+            } else if stats.count == 0 {
+                "count must be at least 1"
+            } else if stats
+                .range
+                .is_some_and(|range| !range.is_finite() || range <= 0.0)
+            {
+                "range must be a positive number"
+            } else if stats
+                .radius
+                .is_some_and(|radius| !radius.is_finite() || radius <= 0.0)
+            {
+                "radius must be a positive number"
+            } else if stats.status.is_some_and(|status| {
+                !status.strength.is_finite()
+                    || status.strength < 0.0
+                    || !status.duration.is_finite()
+                    || status.duration <= 0.0
+            }) {
+                "status needs a positive duration and can not have a negative strength"
+            } else if !(0.0..=1.0).contains(&stats.crit_chance) {
+                "crit_chance must be between 0.0 and 1.0"
+            } else if !stats.crit_multiplier.is_finite() || stats.crit_multiplier < 1.0 {
+                "crit_multiplier must be at least 1.0"
+            } else if !stats.knockback.is_finite() || stats.knockback < 0.0 {
+                "knockback can not be negative"
+            } else if !(0.0..=1.0).contains(&stats.accuracy) {
+                "accuracy must be between 0.0 and 1.0"
+            } else {
+                continue;
+            };
+            return Err(WeaponDefinitionError::InvalidLevel {
+                name: self.name.clone(),
+                level,
+                reason,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct WeaponDefinitionLoader;
+
+impl AssetLoader for WeaponDefinitionLoader {
+    type Asset = WeaponDefinition;
+    type Settings = ();
+    type Error = WeaponDefinitionError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let file = WeaponDefinitionFile::parse(&bytes)?;
+            Ok(WeaponDefinition {
+                projectile_sprite: load_context.load(file.projectile_sprite),
+                projectile_size: file.projectile_size.map(Vec2::splat),
+                multishot: file.multishot,
+                damage_type: file.damage_type,
+                targeting: file.targeting,
+                levels: file.levels,
+            })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["weapon.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status_effects::StatusKind;
+
+    fn valid() -> WeaponDefinitionFile {
+        WeaponDefinitionFile::parse(
+            br#"(
+                name: "Test",
+                projectile_sprite: "textures/bullet.png",
+                levels: [
+                    (cooldown: 1.0, damage: 1, projectile_velocity: 100.0, pierce: 1, lifespan: 1.0),
+                ],
+            )"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn every_weapon_asset_is_valid() {
+        let folder = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/weapons");
+        for entry in std::fs::read_dir(folder).unwrap() {
+            let path = entry.unwrap().path();
+            let bytes = std::fs::read(&path).unwrap();
+            if let Err(error) = WeaponDefinitionFile::parse(&bytes) {
+                panic!("{}: {error}", path.display());
+            }
+        }
+    }
+
+    #[test]
+    fn infinite_cooldown_and_harmless_levels_are_allowed() {
+        let mut file = valid();
+        file.levels[0].cooldown = f32::INFINITY;
+        file.levels[0].damage = 0;
+        file.levels[0].pierce = 0;
+        file.levels[0].lifespan = 0.0;
+        assert!(file.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_projectile_size() {
+        let mut file = valid();
+        file.projectile_size = Some(0.0);
+        assert!(matches!(
+            file.validate(),
+            Err(WeaponDefinitionError::InvalidSize(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_multishot() {
+        for multishot in [Multishot::Spread(-0.1), Multishot::Burst(f32::NAN)] {
+            let mut file = valid();
+            file.multishot = multishot;
+            assert!(matches!(
+                file.validate(),
+                Err(WeaponDefinitionError::InvalidMultishot(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_no_levels() {
+        let mut file = valid();
+        file.levels.clear();
+        assert!(matches!(
+            file.validate(),
+            Err(WeaponDefinitionError::NoLevels(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_levels() {
+        let cases: [(fn(&mut WeaponLevel), &str); 13] = [
+            (|l| l.cooldown = 0.0, "cooldown"),
+            (|l| l.cooldown = f32::NAN, "cooldown"),
+            (|l| l.projectile_velocity = -1.0, "projectile_velocity"),
+            (|l| l.lifespan = -1.0, "lifespan"),
+            (|l| l.damage = -1, "damage"),
+            (|l| l.count = 0, "count"),
+            (|l| l.range = Some(0.0), "range"),
+            (|l| l.radius = Some(-5.0), "radius"),
+            (
+                |l| {
+                    l.status = Some(StatusEffect {
+                        kind: StatusKind::Slow,
+                        strength: 0.5,
+                        duration: 0.0,
+                    })
+                },
+                "status",
+            ),
+            (|l| l.crit_chance = 1.5, "crit_chance"),
+            (|l| l.crit_multiplier = 0.5, "crit_multiplier"),
+            (|l| l.knockback = -1.0, "knockback"),
+            (|l| l.accuracy = -0.1, "accuracy"),
+        ];
+        for (break_level, stat) in cases {
+            let mut file = valid();
+            // the first level is fine, so the error has to point at the second
+            file.levels.push(file.levels[0].clone());
+            break_level(&mut file.levels[1]);
+            match file.validate() {
+                Err(WeaponDefinitionError::InvalidLevel { level, reason, .. }) => {
+                    assert_eq!(level, 1, "{stat}");
+                    assert!(reason.starts_with(stat), "{stat}: {reason}");
+                }
+                other => panic!("{stat} was not rejected: {other:?}"),
+            }
+        }
+    }
+}